@@ -0,0 +1,35 @@
+"""Type stubs for the aoc22 Python bindings."""
+
+def solve(day: int, input: str) -> tuple[str, str | None]:
+    """Solves both parts of `day`. Raises LookupError for unknown days, ValueError for bad input."""
+
+class FileSystem:
+    """The day 7 filesystem, rebuilt from a terminal session."""
+
+    def __init__(self, input: str) -> None: ...
+    def size(self, path: str = "/") -> int: ...
+    def folder_sizes(self) -> list[tuple[str, int]]: ...
+    def __contains__(self, path: str) -> bool: ...
+
+class Forest:
+    """The day 8 forest, as a grid of tree heights."""
+
+    def __init__(self, input: str) -> None: ...
+    @property
+    def width(self) -> int: ...
+    @property
+    def height(self) -> int: ...
+    def tree(self, x: int, y: int) -> int: ...
+    def scenic_score(self, x: int, y: int) -> int: ...
+    def visible(self) -> list[tuple[int, int]]: ...
+
+class Rope:
+    """The day 9 rope, with any number of knots."""
+
+    def __init__(self, knots: int = 2) -> None: ...
+    @property
+    def knots(self) -> list[tuple[int, int]]: ...
+    @property
+    def tail(self) -> tuple[int, int]: ...
+    def step(self, direction: str) -> None: ...
+    def apply(self, motions: str) -> list[tuple[int, int]]: ...
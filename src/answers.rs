@@ -0,0 +1,67 @@
+//! Registry of answers confirmed correct, stored as TOML keyed by day and part:
+//!
+//! ```toml
+//! [day01]
+//! part1 = "71124"
+//! ```
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Answers(BTreeMap<String, BTreeMap<String, String>>);
+
+impl Answers {
+    /// Loads the registry, starting an empty one if the file doesn't exist yet.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(path)
+            .context(format!("could not read answers '{}'", path.display()))?;
+        toml::from_str(&contents).context(format!("invalid answers '{}'", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, toml::to_string(self)?)
+            .context(format!("could not write answers '{}'", path.display()))
+    }
+
+    pub fn get(&self, day: u32, part: usize) -> Option<&str> {
+        self.0
+            .get(&format!("day{:02}", day))
+            .and_then(|parts| parts.get(&format!("part{}", part)))
+            .map(String::as_str)
+    }
+
+    pub fn set(&mut self, day: u32, part: usize, answer: impl Into<String>) {
+        self.0
+            .entry(format!("day{:02}", day))
+            .or_default()
+            .insert(format!("part{}", part), answer.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut answers = Answers::default();
+        answers.set(1, 1, "71124");
+        answers.set(9, 2, "2449");
+
+        let toml = toml::to_string(&answers).unwrap();
+        assert_eq!(
+            "[day01]\npart1 = \"71124\"\n\n[day09]\npart2 = \"2449\"\n",
+            toml
+        );
+
+        let answers: Answers = toml::from_str(&toml).unwrap();
+        assert_eq!(Some("71124"), answers.get(1, 1));
+        assert_eq!(None, answers.get(1, 2));
+    }
+}
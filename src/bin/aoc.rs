@@ -0,0 +1,489 @@
+use anyhow::{anyhow, Context, Result};
+use aoc22::cli::Options;
+use aoc22::config::Config;
+use aoc22::detect::{self, MIN_CONFIDENCE};
+use aoc22::fetch::Client;
+use aoc22::inputs::{self, Key};
+use aoc22::leaderboard::Leaderboard;
+use aoc22::lint;
+use aoc22::runner::{self, Status};
+use aoc22::status;
+use aoc22::submit::{self, Journal, Verdict};
+
+const USAGE: &str = "usage: aoc lint <day> [--example] [--input-set <name>] [input]
+       aoc run <day|all> [--part <n>] [--example] [--variant <name|all>] [input]
+                         [--format <text|json>] [--precision <s|ms|us|ns>]
+                         [--timeout <secs>] [--input-set <name>]
+       aoc run --auto <input> [--part <n>] [--variant <name|all>] [--format <text|json>]
+       aoc detect <input> [--input-set <name>]
+       aoc top <k> [input] [--example] [--format <text|json>] [--input-set <name>]
+       aoc elves [over <calories>] [input] [--example] [--format <text|json>]
+                 [--input-set <name>]
+       aoc rebalance [exact|heuristic|auto] [input] [--example] [--format <text|json>]
+                     [--input-set <name>]
+       aoc game <game.toml> [input] [--example] [--format <text|json>] [--input-set <name>]
+       aoc mappings [input] [--game <game.toml>] [--target <score>] [--example]
+                    [--format <text|json>] [--input-set <name>]
+       aoc tournament [input] [--game <game.toml>] [--order <k>] [--example]
+                      [--format <text|json>] [--input-set <name>]
+       aoc badges [input] [--size <n>] [--example] [--format <text|json>] [--input-set <name>]
+       aoc regroup [input] [--example] [--format <text|json>] [--input-set <name>]
+       aoc repack [input] [--example] [--format <text|json>] [--input-set <name>]
+       aoc inputs <lock|unlock> [--input-set <name>]
+       aoc fetch <day> [--input-set <name>]
+       aoc submit <day> <part> [--input-set <name>]
+       aoc status [--format <text|json>] [--precision <s|ms|us|ns>] [--input-set <name>]
+       aoc leaderboard <file.json> [--format <text|json>]";
+
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match args[..] {
+        ["lint", day, ref rest @ ..] => lint_input(parse_day(day)?, parse_options(rest)?),
+        ["run", "--auto", ref rest @ ..] => run_auto(parse_options(rest)?),
+        ["run", "all", ref rest @ ..] => run_all(parse_options(rest)?),
+        ["run", day, ref rest @ ..] => run(parse_day(day)?, parse_options(rest)?),
+        ["fetch", day, ref rest @ ..] => fetch(parse_day(day)?, parse_options(rest)?),
+        ["submit", day, part, ref rest @ ..] => {
+            let options = Options::parse(["--part", part].into_iter().chain(rest.iter().copied()))?;
+            submit(parse_day(day)?, options)
+        }
+        ["detect", ref rest @ ..] => detect_day(parse_options(rest)?),
+        #[cfg(feature = "day01")]
+        ["top", k, ref rest @ ..] => top_elves(k, parse_options(rest)?),
+        #[cfg(feature = "day01")]
+        ["elves", "over", calories, ref rest @ ..] => {
+            let calories = calories
+                .parse()
+                .context(format!("'{}' is not a valid calorie count", calories))?;
+            elves(Some(calories), parse_options(rest)?)
+        }
+        #[cfg(feature = "day01")]
+        ["elves", ref rest @ ..] => elves(None, parse_options(rest)?),
+        #[cfg(feature = "day01")]
+        ["rebalance", method @ ("exact" | "heuristic" | "auto"), ref rest @ ..] => {
+            rebalance(method.parse()?, parse_options(rest)?)
+        }
+        #[cfg(feature = "day01")]
+        ["rebalance", ref rest @ ..] => rebalance(Default::default(), parse_options(rest)?),
+        #[cfg(feature = "day02")]
+        ["game", file, ref rest @ ..] => play_game(file, parse_options(rest)?),
+        #[cfg(feature = "day02")]
+        ["mappings", ref rest @ ..] => {
+            let (game, rest) = take_value(rest, "--game")?;
+            let (target, rest) = take_value(&rest, "--target")?;
+            let target = target
+                .map(|t| t.parse().context(format!("'{}' is not a valid score", t)))
+                .transpose()?;
+            mappings(game, target, parse_options(&rest)?)
+        }
+        #[cfg(feature = "day02")]
+        ["tournament", ref rest @ ..] => {
+            let (game, rest) = take_value(rest, "--game")?;
+            let (order, rest) = take_value(&rest, "--order")?;
+            let order = order
+                .map(|k| k.parse().context(format!("'{}' is not a valid order", k)))
+                .transpose()?;
+            tournament(game, order.unwrap_or(2), parse_options(&rest)?)
+        }
+        #[cfg(feature = "day03")]
+        ["badges", ref rest @ ..] => {
+            let (size, rest) = take_value(rest, "--size")?;
+            let size = size
+                .map(|n| {
+                    n.parse()
+                        .context(format!("'{}' is not a valid group size", n))
+                })
+                .transpose()?;
+            badges(size.unwrap_or(3), parse_options(&rest)?)
+        }
+        #[cfg(feature = "day03")]
+        ["regroup", ref rest @ ..] => regroup(parse_options(rest)?),
+        #[cfg(feature = "day03")]
+        ["repack", ref rest @ ..] => repack(parse_options(rest)?),
+        ["status", ref rest @ ..] => show_status(parse_options(rest)?),
+        ["leaderboard", ref rest @ ..] => leaderboard(parse_options(rest)?),
+        ["inputs", "lock", ref rest @ ..] => lock_inputs(parse_options(rest)?),
+        ["inputs", "unlock", ref rest @ ..] => unlock_inputs(parse_options(rest)?),
+        _ => Err(anyhow!(USAGE)),
+    }
+}
+
+fn parse_day(day: &str) -> Result<u32> {
+    day.parse().context(format!("'{}' is not a valid day", day))
+}
+
+/// Pulls a command-specific `--flag value` (or `--flag=value`) out of `args`, leaving the rest for
+/// [`parse_options`].
+#[cfg(any(feature = "day02", feature = "day03"))]
+fn take_value<'a>(args: &[&'a str], flag: &str) -> Result<(Option<&'a str>, Vec<&'a str>)> {
+    let mut value = None;
+    let mut rest = vec![];
+    let mut args = args.iter().copied();
+
+    while let Some(arg) = args.next() {
+        if arg == flag {
+            value = Some(args.next().context(format!("{} needs a value", flag))?);
+        } else if let Some(v) = arg.strip_prefix(flag).and_then(|v| v.strip_prefix('=')) {
+            value = Some(v);
+        } else {
+            rest.push(arg);
+        }
+    }
+    Ok((value, rest))
+}
+
+fn parse_options(args: &[&str]) -> Result<Options> {
+    Options::parse(args.iter().copied())
+}
+
+fn lint_input(day: u32, options: Options) -> Result<()> {
+    if options.part.is_some() {
+        return Err(anyhow!("--part has no effect on lint"));
+    }
+    let config = Config::load()?.with_options(&options);
+    let input = runner::read_input(day, &config, &options)?;
+
+    let violations = lint::lint(day, &input)?;
+    for violation in violations.iter() {
+        println!("{}", violation);
+    }
+
+    if violations.is_empty() {
+        println!("day {}: ok", day);
+        Ok(())
+    } else {
+        Err(anyhow!("{} problem(s) found", violations.len()))
+    }
+}
+
+fn run(day: u32, options: Options) -> Result<()> {
+    let config = Config::load()?.with_options(&options);
+    runner::execute(day, &config, &options)
+}
+
+fn run_auto(options: Options) -> Result<()> {
+    let config = Config::load()?.with_options(&options);
+    let guess = guess_day(&config, &options)?;
+    if guess.confidence < MIN_CONFIDENCE {
+        return Err(anyhow!(
+            "can't tell which day this input is for (best guess is day {}, {:.0}% confident)",
+            guess.day,
+            guess.confidence * 100.0
+        ));
+    }
+
+    eprintln!(
+        "looks like day {} ({:.0}% confident)",
+        guess.day,
+        guess.confidence * 100.0
+    );
+    runner::execute(guess.day, &config, &options)
+}
+
+fn run_all(options: Options) -> Result<()> {
+    if options.input.is_some() {
+        return Err(anyhow!("an input file can only be given for a single day"));
+    }
+
+    for &day in runner::SOLVED {
+        match Status::of(day) {
+            Status::Enabled => run(day, options.clone())?,
+            _ => println!(
+                "day {:02} disabled (enable the `day{:02}` feature)",
+                day, day
+            ),
+        }
+    }
+    Ok(())
+}
+
+fn detect_day(options: Options) -> Result<()> {
+    let config = Config::load()?.with_options(&options);
+    let input = read_unlabelled(&config, &options)?;
+
+    let guesses = detect::detect(&input);
+    for (i, guess) in guesses.iter().filter(|g| g.confidence > 0.0).enumerate() {
+        let label = if i == 0 { "best guess" } else { "or maybe" };
+        println!(
+            "{}: day {} ({:.0}% confident)",
+            label,
+            guess.day,
+            guess.confidence * 100.0
+        );
+    }
+
+    match guesses.first() {
+        Some(best) if best.confidence >= MIN_CONFIDENCE => Ok(()),
+        _ => Err(anyhow!("no day matches this input confidently")),
+    }
+}
+
+fn guess_day(config: &Config, options: &Options) -> Result<detect::Guess> {
+    let input = read_unlabelled(config, options)?;
+    detect::detect(&input)
+        .into_iter()
+        .next()
+        .context("no days to guess from")
+}
+
+fn read_unlabelled(config: &Config, options: &Options) -> Result<String> {
+    let path = options.input.as_ref().context("no input file given")?;
+    inputs::read(path, &config.key_file)
+}
+
+#[cfg(feature = "day01")]
+fn top_elves(k: &str, options: Options) -> Result<()> {
+    use aoc22::days::day01;
+
+    let k = k
+        .parse()
+        .context(format!("'{}' is not a valid number of elves", k))?;
+    let config = Config::load()?.with_options(&options);
+    let top = day01::top_k(runner::open_input(1, &config, &options)?, k)?;
+    println!("{}", day01::render_top(&top, config.format)?);
+    Ok(())
+}
+
+#[cfg(feature = "day01")]
+fn elves(over: Option<u32>, options: Options) -> Result<()> {
+    use aoc22::days::day01::Inventory;
+
+    let config = Config::load()?.with_options(&options);
+    let inventory: Inventory = runner::read_input(1, &config, &options)?.parse()?;
+    println!("{}", inventory.report(over, config.format)?);
+    Ok(())
+}
+
+#[cfg(feature = "day01")]
+fn rebalance(method: aoc22::days::day01::rebalance::Method, options: Options) -> Result<()> {
+    use aoc22::days::day01::{rebalance, Inventory};
+
+    let config = Config::load()?.with_options(&options);
+    let inventory: Inventory = runner::read_input(1, &config, &options)?.parse()?;
+    let plan = rebalance::rebalance(&inventory, method)?;
+    println!("{}", plan.render(&inventory.elves, config.format)?);
+    Ok(())
+}
+
+#[cfg(feature = "day02")]
+fn play_game(file: &str, options: Options) -> Result<()> {
+    use aoc22::days::day02::Game;
+    use aoc22::output::Format;
+
+    let config = Config::load()?.with_options(&options);
+    let game = Game::load(file.as_ref())?;
+    let input = runner::read_input(2, &config, &options)?;
+
+    // With more than three shapes the two strategies read the player's column differently, so a
+    // guide may only make sense under one of them
+    let scores: Vec<_> = [Game::score_strategy_one, Game::score_strategy_two]
+        .into_iter()
+        .map(|strategy| {
+            input
+                .lines()
+                .map(|round| strategy(&game, round))
+                .sum::<Result<u32>>()
+        })
+        .collect();
+    if scores.iter().all(Result::is_err) {
+        return Err(anyhow!(
+            "the guide doesn't fit either strategy: {:#}",
+            scores[0].as_ref().unwrap_err()
+        ));
+    }
+
+    match config.format {
+        Format::Text => {
+            for (name, score) in ["one", "two"].iter().zip(scores.iter()) {
+                match score {
+                    Ok(score) => println!("strategy {}: {}", name, score),
+                    Err(err) => println!("strategy {}: doesn't apply ({:#})", name, err),
+                }
+            }
+        }
+        Format::Json => {
+            let scores: Vec<_> = scores.iter().map(|score| score.as_ref().ok()).collect();
+            println!(
+                "{}",
+                serde_json::json!({"one": scores[0], "two": scores[1]})
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "day02")]
+fn mappings(game: Option<&str>, target: Option<u64>, options: Options) -> Result<()> {
+    use aoc22::days::day02::{mapping, Game};
+
+    let config = Config::load()?.with_options(&options);
+    let game = match game {
+        Some(path) => Game::load(path.as_ref())?,
+        None => Game::default(),
+    };
+    let guide = runner::read_input(2, &config, &options)?;
+    println!("{}", mapping::render(&game, &guide, target, config.format)?);
+    Ok(())
+}
+
+#[cfg(feature = "day02")]
+fn tournament(game: Option<&str>, order: usize, options: Options) -> Result<()> {
+    use aoc22::days::day02::{tournament, Game};
+
+    let config = Config::load()?.with_options(&options);
+    let game = match game {
+        Some(path) => Game::load(path.as_ref())?,
+        None => Game::default(),
+    };
+    let guide = tournament::parse(&game, &runner::read_input(2, &config, &options)?)?;
+    let standings = tournament::tournament(&game, &guide, order);
+    println!("{}", tournament::render(&standings, &guide, config.format)?);
+    Ok(())
+}
+
+#[cfg(feature = "day03")]
+fn badges(size: usize, options: Options) -> Result<()> {
+    use aoc22::days::day03;
+
+    let config = Config::load()?.with_options(&options);
+    let input = runner::read_input(3, &config, &options)?;
+    let groups = day03::groups(&input, size)?;
+    println!("{}", day03::render_groups(&groups, config.format)?);
+    Ok(())
+}
+
+#[cfg(feature = "day03")]
+fn regroup(options: Options) -> Result<()> {
+    use aoc22::days::day03::partition;
+
+    let config = Config::load()?.with_options(&options);
+    let input = runner::read_input(3, &config, &options)?;
+    let partition = partition::partition(&input)?;
+    println!("{}", partition.render(config.format)?);
+    Ok(())
+}
+
+#[cfg(feature = "day03")]
+fn repack(options: Options) -> Result<()> {
+    use aoc22::days::day03::repack;
+
+    let config = Config::load()?.with_options(&options);
+    let input = runner::read_input(3, &config, &options)?;
+    let plans = repack::plan_all(&input)?;
+    println!("{}", repack::render(&plans, config.format)?);
+    Ok(())
+}
+
+fn fetch(day: u32, options: Options) -> Result<()> {
+    let config = Config::load()?.with_options(&options);
+    let path = config.input_path(day);
+
+    if path.exists() {
+        println!("day {} already cached in '{}'", day, path.display());
+    } else {
+        Client::from_config(&config)?.fetch(day, &path)?;
+        println!("saved day {} to '{}'", day, path.display());
+    }
+    Ok(())
+}
+
+fn submit(day: u32, options: Options) -> Result<()> {
+    if options.example || options.input.is_some() {
+        return Err(anyhow!(
+            "only answers for the puzzle input can be submitted"
+        ));
+    }
+    let config = Config::load()?.with_options(&options);
+    let part = options.part.context("no part to submit")?;
+
+    let report = runner::find(day)?.run(&config, &options)?;
+    let answer = &report
+        .answers
+        .first()
+        .context("no answer to submit")?
+        .answer;
+
+    let client = Client::from_config(&config)?;
+    let mut journal = Journal::load(&config.journal_file)?;
+    let verdict = submit::submit(
+        &client,
+        &mut journal,
+        &config.answers_file,
+        day,
+        part,
+        answer,
+    )?;
+
+    let message = match verdict {
+        Verdict::Correct => "correct!",
+        Verdict::TooHigh => "too high",
+        Verdict::TooLow => "too low",
+        Verdict::Wrong => "wrong",
+        Verdict::Throttled => "not judged, submitted too recently",
+        Verdict::AlreadySolved => "not judged, this part is already solved",
+    };
+    println!("day {} part {}: {} is {}", day, part, answer, message);
+    Ok(())
+}
+
+fn show_status(options: Options) -> Result<()> {
+    let config = Config::load()?.with_options(&options);
+    let days = status::gather(&config)?;
+    println!(
+        "{}",
+        status::render(&days, config.format, config.precision)?
+    );
+    Ok(())
+}
+
+fn leaderboard(options: Options) -> Result<()> {
+    let path = options
+        .input
+        .as_ref()
+        .context("no leaderboard file given")?;
+    let config = Config::load()?.with_options(&options);
+
+    let contents =
+        std::fs::read_to_string(path).context(format!("could not read '{}'", path.display()))?;
+    let leaderboard: Leaderboard = contents
+        .parse()
+        .context(format!("invalid leaderboard '{}'", path.display()))?;
+    println!("{}", leaderboard.render(config.format)?);
+    Ok(())
+}
+
+fn lock_inputs(options: Options) -> Result<()> {
+    let config = Config::load()?.with_options(&options);
+
+    let key = if config.key_file.is_file() {
+        Key::load(&config.key_file)?
+    } else {
+        let key = Key::generate();
+        key.save(&config.key_file)?;
+        println!("generated new key in '{}'", config.key_file.display());
+        key
+    };
+
+    for path in inputs::lock_dir(&config.input_dir(), &key)? {
+        println!("locked '{}'", path.display());
+    }
+    Ok(())
+}
+
+fn unlock_inputs(options: Options) -> Result<()> {
+    let config = Config::load()?.with_options(&options);
+    if !config.key_file.is_file() {
+        return Err(anyhow!(
+            "no key file at '{}', can't unlock inputs",
+            config.key_file.display()
+        ));
+    }
+    let key = Key::load(&config.key_file)?;
+
+    for path in inputs::unlock_dir(&config.input_dir(), &key)? {
+        println!("unlocked '{}'", path.display());
+    }
+    Ok(())
+}
@@ -0,0 +1,120 @@
+//! Command-line options shared by the day binaries and `aoc run`.
+
+use crate::output::{Format, Precision};
+use anyhow::{anyhow, Context, Result};
+use std::path::PathBuf;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// Only run this part (1-based). Runs every part if unset.
+    pub part: Option<usize>,
+    /// Use the day's example fixture instead of the puzzle input.
+    pub example: bool,
+    /// Explicit input file, overriding the default path.
+    pub input: Option<PathBuf>,
+    pub format: Option<Format>,
+    pub precision: Option<Precision>,
+    /// Timeout in seconds, overriding every timeout in the config.
+    pub timeout: Option<u64>,
+    pub input_set: Option<String>,
+    /// Alternative implementation to run, or `all` to cross-check every one.
+    pub variant: Option<String>,
+}
+
+impl Options {
+    pub fn from_env() -> Result<Self> {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        Self::parse(args.iter().map(String::as_str))
+    }
+
+    /// Parses `--part <n>`, `--example`, `--variant <name|all>`, the config overrides (`--format`,
+    /// `--precision`, `--timeout`, `--input-set`) and an optional input path. Options taking a
+    /// value accept both `--flag value` and `--flag=value`.
+    pub fn parse<'a>(args: impl IntoIterator<Item = &'a str>) -> Result<Self> {
+        let mut options = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let (flag, value) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag, Some(value)),
+                _ => (arg, None),
+            };
+            let mut value = || {
+                value
+                    .or_else(|| args.next())
+                    .context(format!("{} needs a value", flag))
+            };
+
+            match flag {
+                "--part" => options.part = Some(parse_part(value()?)?),
+                "--example" => options.example = true,
+                "--format" => options.format = Some(value()?.parse()?),
+                "--precision" => options.precision = Some(value()?.parse()?),
+                "--timeout" => {
+                    let timeout = value()?;
+                    let timeout = timeout
+                        .parse()
+                        .context(format!("'{}' is not a valid timeout", timeout))?;
+                    options.timeout = Some(timeout);
+                }
+                "--input-set" => options.input_set = Some(value()?.into()),
+                "--variant" => options.variant = Some(value()?.into()),
+                flag if flag.starts_with('-') => return Err(anyhow!("unknown option '{}'", flag)),
+                path if options.input.is_none() => options.input = Some(path.into()),
+                path => return Err(anyhow!("unexpected argument '{}'", path)),
+            }
+        }
+
+        if options.example && options.input.is_some() {
+            return Err(anyhow!("--example can't be combined with an input file"));
+        }
+
+        Ok(options)
+    }
+}
+
+fn parse_part(part: &str) -> Result<usize> {
+    match part.parse() {
+        Ok(part) if part > 0 => Ok(part),
+        _ => Err(anyhow!("'{}' is not a valid part", part)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Options::default(), Options::parse([]).unwrap());
+
+        let options = Options::parse(["--part", "2", "--example"]).unwrap();
+        assert_eq!(Some(2), options.part);
+        assert!(options.example);
+
+        let options = Options::parse(["input.txt", "--part=1"]).unwrap();
+        assert_eq!(Some(1), options.part);
+        assert_eq!(Some(PathBuf::from("input.txt")), options.input);
+
+        let options =
+            Options::parse(["--format=json", "--precision", "us", "--timeout=5"]).unwrap();
+        assert_eq!(Some(Format::Json), options.format);
+        assert_eq!(Some(Precision::Us), options.precision);
+        assert_eq!(Some(5), options.timeout);
+
+        let options = Options::parse(["--variant", "all"]).unwrap();
+        assert_eq!(Some("all".to_string()), options.variant);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(Options::parse(["--part"]).is_err());
+        assert!(Options::parse(["--part", "0"]).is_err());
+        assert!(Options::parse(["--part=x"]).is_err());
+        assert!(Options::parse(["--verbose"]).is_err());
+        assert!(Options::parse(["a.txt", "b.txt"]).is_err());
+        assert!(Options::parse(["--example", "a.txt"]).is_err());
+        assert!(Options::parse(["--format", "yaml"]).is_err());
+        assert!(Options::parse(["--timeout", "soon"]).is_err());
+    }
+}
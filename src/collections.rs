@@ -0,0 +1,275 @@
+//! Collections for the hot loops in the solutions.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasherDefault, Hasher};
+
+/// The multiply-and-rotate hash from rustc ("FxHash"). Much quicker than the default SipHash for
+/// small keys like chars and coordinates, but with no defence against crafted collisions, which
+/// puzzle inputs don't need.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FastHasher(u64);
+
+const SEED: u64 = 0x517c_c1b7_2722_0a95;
+
+impl FastHasher {
+    fn add(&mut self, word: u64) {
+        self.0 = (self.0.rotate_left(5) ^ word).wrapping_mul(SEED);
+    }
+}
+
+impl Hasher for FastHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut words = bytes.chunks_exact(8);
+        for word in &mut words {
+            self.add(u64::from_le_bytes(word.try_into().unwrap()));
+        }
+        for &byte in words.remainder() {
+            self.add(byte as u64);
+        }
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.add(i as u64);
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.add(i as u64);
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.add(i as u64);
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.add(i);
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.add(i as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+pub type FastHashSet<T> = HashSet<T, BuildHasherDefault<FastHasher>>;
+pub type FastHashMap<K, V> = HashMap<K, V, BuildHasherDefault<FastHasher>>;
+
+/// A set of grid coordinates, stored as one bit per cell of the bounding box seen so far.
+///
+/// The box grows in whichever direction it needs to, with some slack so that a walk heading one
+/// way doesn't regrow it on every step. Suits coordinates that stay in a compact region; a few far
+/// apart points would make for a very large bitmap.
+#[derive(Debug, Clone, Default)]
+pub struct DenseSet {
+    min_x: i64,
+    min_y: i64,
+    width: usize,
+    height: usize,
+    bits: Vec<u64>,
+    len: usize,
+}
+
+impl DenseSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a coordinate, returning whether it was new.
+    pub fn insert(&mut self, x: i32, y: i32) -> bool {
+        let idx = match self.index(x, y) {
+            Some(idx) => idx,
+            None => {
+                self.grow(x as i64, y as i64);
+                self.index(x, y).unwrap()
+            }
+        };
+
+        let (word, bit) = (idx / 64, 1 << (idx % 64));
+        let new = self.bits[word] & bit == 0;
+        self.bits[word] |= bit;
+        self.len += new as usize;
+        new
+    }
+
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        self.index(x, y)
+            .is_some_and(|idx| self.bits[idx / 64] & (1 << (idx % 64)) != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The coordinates in the set, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.bits
+            .iter()
+            .enumerate()
+            .filter(|(_, &word)| word != 0)
+            .flat_map(|(word_idx, &word)| {
+                (0..64)
+                    .filter(move |bit| word & (1 << bit) != 0)
+                    .map(move |bit| word_idx * 64 + bit)
+            })
+            .map(|idx| {
+                let x = self.min_x + (idx % self.width) as i64;
+                let y = self.min_y + (idx / self.width) as i64;
+                (x as i32, y as i32)
+            })
+    }
+
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        let (dx, dy) = (x as i64 - self.min_x, y as i64 - self.min_y);
+        if dx < 0 || dy < 0 || dx >= self.width as i64 || dy >= self.height as i64 {
+            return None;
+        }
+        Some(dy as usize * self.width + dx as usize)
+    }
+
+    // Rebuilds the bitmap to cover (x, y), padding every side that had to move by half the new
+    // extent again
+    fn grow(&mut self, x: i64, y: i64) {
+        let (mut min_x, mut min_y) = (self.min_x.min(x), self.min_y.min(y));
+        let mut max_x = (self.min_x + self.width as i64 - 1).max(x);
+        let mut max_y = (self.min_y + self.height as i64 - 1).max(y);
+        if self.width == 0 {
+            (min_x, min_y, max_x, max_y) = (x, y, x, y);
+        }
+
+        let pad_x = ((max_x - min_x + 1) / 2).max(8);
+        let pad_y = ((max_y - min_y + 1) / 2).max(8);
+        if self.width == 0 || min_x < self.min_x {
+            min_x -= pad_x;
+        }
+        if self.width == 0 || max_x >= self.min_x + self.width as i64 {
+            max_x += pad_x;
+        }
+        if self.height == 0 || min_y < self.min_y {
+            min_y -= pad_y;
+        }
+        if self.height == 0 || max_y >= self.min_y + self.height as i64 {
+            max_y += pad_y;
+        }
+
+        let old = std::mem::take(self);
+        self.min_x = min_x;
+        self.min_y = min_y;
+        self.width = (max_x - min_x + 1) as usize;
+        self.height = (max_y - min_y + 1) as usize;
+        self.bits = vec![0; (self.width * self.height).div_ceil(64)];
+        self.extend(old.iter());
+    }
+}
+
+impl Extend<(i32, i32)> for DenseSet {
+    fn extend<I: IntoIterator<Item = (i32, i32)>>(&mut self, iter: I) {
+        for (x, y) in iter {
+            self.insert(x, y);
+        }
+    }
+}
+
+impl FromIterator<(i32, i32)> for DenseSet {
+    fn from_iter<I: IntoIterator<Item = (i32, i32)>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hash::BuildHasher;
+
+    #[test]
+    fn test_dense_set() {
+        let mut set = DenseSet::new();
+        assert!(set.is_empty());
+
+        assert!(set.insert(0, 0));
+        assert!(!set.insert(0, 0));
+        assert!(set.insert(-100, 3));
+        assert!(set.insert(250, -40));
+
+        assert_eq!(3, set.len());
+        assert!(set.contains(0, 0));
+        assert!(set.contains(-100, 3));
+        assert!(!set.contains(-100, 4));
+        assert!(!set.contains(1000, 1000));
+    }
+
+    #[test]
+    fn test_iter_row_order() {
+        let points = [(3, 1), (-2, 5), (0, 0), (7, -9), (3, 1)];
+        let set: DenseSet = points.into_iter().collect();
+
+        assert_eq!(
+            vec![(7, -9), (0, 0), (3, 1), (-2, 5)],
+            set.iter().collect::<Vec<_>>()
+        );
+    }
+
+    // Compares the sets on the access patterns of the solutions. Run with
+    // `cargo test --release collections -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn bench_sets() {
+        use crate::Timer;
+
+        // A rope tail wandering about, as in day 9, and the runs of chars from days 3 and 6
+        let walk: Vec<(i32, i32)> = (0..2_000_000)
+            .scan((0i32, 0i32), |(x, y), i: i32| {
+                match (i / 7 + i / 13) % 4 {
+                    0 => *x += 1,
+                    1 => *y += 1,
+                    2 => *x -= 1,
+                    _ => *y -= 1,
+                }
+                Some((*x % 200, *y % 200))
+            })
+            .collect();
+        let chars: Vec<char> = (0..2_000_000)
+            .map(|i| (b'a' + (i * 7 % 26) as u8) as char)
+            .collect();
+
+        let timer = Timer::tick();
+        let set: HashSet<_> = walk.iter().copied().collect();
+        println!("walk, HashSet:     {:?} ({})", timer.elapsed(), set.len());
+        let timer = Timer::tick();
+        let set: FastHashSet<_> = walk.iter().copied().collect();
+        println!("walk, FastHashSet: {:?} ({})", timer.elapsed(), set.len());
+        let timer = Timer::tick();
+        let set: DenseSet = walk.iter().copied().collect();
+        println!("walk, DenseSet:    {:?} ({})", timer.elapsed(), set.len());
+
+        let timer = Timer::tick();
+        let unique: usize = chars
+            .windows(14)
+            .map(|w| w.iter().collect::<HashSet<_>>().len())
+            .sum();
+        println!("chars, HashSet:     {:?} ({})", timer.elapsed(), unique);
+        let timer = Timer::tick();
+        let unique: usize = chars
+            .windows(14)
+            .map(|w| w.iter().collect::<FastHashSet<_>>().len())
+            .sum();
+        println!("chars, FastHashSet: {:?} ({})", timer.elapsed(), unique);
+    }
+
+    #[test]
+    fn test_fast_hasher() {
+        let build = BuildHasherDefault::<FastHasher>::default();
+        assert_eq!(build.hash_one('a'), build.hash_one('a'));
+        assert_ne!(build.hash_one('a'), build.hash_one('b'));
+
+        let set: FastHashSet<_> = "hello".chars().collect();
+        assert_eq!(4, set.len());
+    }
+}
@@ -0,0 +1,236 @@
+//! Project configuration, read from `aoc.toml`.
+//!
+//! The file is looked up in the current directory and then in each directory above it. Relative
+//! paths, whether set in the file or left to their defaults, are resolved against the directory the
+//! file lives in, or against the current directory when there is no file. Every setting is
+//! optional, and anything given on the command line wins over the file.
+
+use crate::cli::Options;
+use crate::fetch;
+use crate::output::{Format, Precision};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub const FILE_NAME: &str = "aoc.toml";
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    /// Directory the config file was found in, or empty for the current directory.
+    #[serde(skip)]
+    pub root: PathBuf,
+    /// Directory holding the puzzle inputs.
+    pub data_dir: PathBuf,
+    /// Directory holding the example inputs.
+    pub example_dir: PathBuf,
+    /// Named set of inputs to use, read from `<data-dir>/<input-set>/`. Uses the inputs directly
+    /// in `data-dir` if unset.
+    pub input_set: Option<String>,
+    /// Key used to decrypt locked inputs. Keep this out of version control.
+    pub key_file: PathBuf,
+    pub format: Format,
+    pub precision: Precision,
+    /// Timeout in seconds applied to every day.
+    pub timeout: Option<u64>,
+    /// Per-day timeouts in seconds, keyed by `dayNN`. These win over `timeout`.
+    pub timeouts: HashMap<String, u64>,
+    pub year: u32,
+    /// Site to download inputs from.
+    pub base_url: String,
+    /// Session token for downloads. `AOC_SESSION` in the environment wins over this.
+    pub session: Option<String>,
+    pub user_agent: String,
+    /// Minimum number of seconds between requests to the site.
+    pub fetch_interval: u64,
+    /// Registry of answers confirmed correct.
+    pub answers_file: PathBuf,
+    /// Local record of every submitted answer.
+    pub journal_file: PathBuf,
+    /// Timing of the last full run of each day, shown by `aoc status`.
+    pub timings_file: PathBuf,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            root: PathBuf::new(),
+            data_dir: "data".into(),
+            example_dir: "data".into(),
+            input_set: None,
+            key_file: ".aoc-key".into(),
+            format: Format::default(),
+            precision: Precision::default(),
+            timeout: None,
+            timeouts: HashMap::new(),
+            year: 2022,
+            base_url: "https://adventofcode.com".into(),
+            session: None,
+            user_agent: fetch::USER_AGENT.into(),
+            fetch_interval: 5,
+            answers_file: "answers.toml".into(),
+            journal_file: "journal.jsonl".into(),
+            timings_file: ".timings.toml".into(),
+        }
+    }
+}
+
+impl Config {
+    /// Loads the nearest `aoc.toml`, falling back to the defaults if there is none.
+    pub fn load() -> Result<Self> {
+        let cwd = std::env::current_dir().context("could not read the current directory")?;
+        match find(&cwd) {
+            Some(path) => Self::from_file(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .context(format!("could not read config '{}'", path.display()))?;
+        let base = path.parent().unwrap_or_else(|| Path::new("."));
+        Self::parse(&contents, base).context(format!("invalid config '{}'", path.display()))
+    }
+
+    /// Parses a config, resolving relative paths against `base`.
+    pub fn parse(contents: &str, base: &Path) -> Result<Self> {
+        let mut config: Self = toml::from_str(contents)?;
+        config.root = base.to_path_buf();
+        config.data_dir = base.join(&config.data_dir);
+        config.example_dir = base.join(&config.example_dir);
+        config.key_file = base.join(&config.key_file);
+        config.answers_file = base.join(&config.answers_file);
+        config.journal_file = base.join(&config.journal_file);
+        config.timings_file = base.join(&config.timings_file);
+        Ok(config)
+    }
+
+    /// Applies the overrides given on the command line.
+    pub fn with_options(mut self, options: &Options) -> Self {
+        if let Some(format) = options.format {
+            self.format = format;
+        }
+        if let Some(precision) = options.precision {
+            self.precision = precision;
+        }
+        if let Some(timeout) = options.timeout {
+            self.timeout = Some(timeout);
+            self.timeouts.clear();
+        }
+        if let Some(input_set) = &options.input_set {
+            self.input_set = Some(input_set.clone());
+        }
+        self
+    }
+
+    pub fn timeout(&self, day: u32) -> Option<Duration> {
+        self.timeouts
+            .get(&format!("day{:02}", day))
+            .or(self.timeout.as_ref())
+            .map(|&secs| Duration::from_secs(secs))
+    }
+
+    /// Directory holding the active input set.
+    pub fn input_dir(&self) -> PathBuf {
+        match &self.input_set {
+            Some(set) => self.data_dir.join(set),
+            None => self.data_dir.clone(),
+        }
+    }
+
+    /// Path to a day's puzzle input in the active input set.
+    pub fn input_path(&self, day: u32) -> PathBuf {
+        self.input_dir().join(format!("day{:02}.txt", day))
+    }
+
+    /// Path to a day's example input, taken from the puzzle description.
+    pub fn example_path(&self, day: u32) -> PathBuf {
+        self.example_dir.join(format!("day{:02}-ex.txt", day))
+    }
+}
+
+// The config file in `dir` or the closest directory above it
+fn find(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .map(|dir| dir.join(FILE_NAME))
+        .find(|path| path.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let contents = r#"
+            data-dir = "inputs"
+            example-dir = "/tmp/examples"
+            input-set = "work"
+            format = "json"
+            precision = "us"
+            timeout = 10
+
+            [timeouts]
+            day08 = 60
+        "#;
+        let config = Config::parse(contents, Path::new("/repo")).unwrap();
+
+        assert_eq!(
+            PathBuf::from("/repo/inputs/work/day09.txt"),
+            config.input_path(9)
+        );
+        assert_eq!(
+            PathBuf::from("/tmp/examples/day09-ex.txt"),
+            config.example_path(9)
+        );
+        assert_eq!(Format::Json, config.format);
+        assert_eq!(Precision::Us, config.precision);
+        assert_eq!(Some(Duration::from_secs(60)), config.timeout(8));
+        assert_eq!(Some(Duration::from_secs(10)), config.timeout(9));
+    }
+
+    #[test]
+    fn test_default_paths() {
+        // Without a file, paths are relative to wherever the command runs
+        let config = Config::default();
+        assert_eq!(PathBuf::from("data/day01.txt"), config.input_path(1));
+        assert_eq!(PathBuf::from(".aoc-key"), config.key_file);
+
+        // With one, defaults are relative to the file even if it doesn't set them
+        let config = Config::parse("year = 2022", Path::new("/repo")).unwrap();
+        assert_eq!(PathBuf::from("/repo"), config.root);
+        assert_eq!(PathBuf::from("/repo/data/day01.txt"), config.input_path(1));
+        assert_eq!(PathBuf::from("/repo/answers.toml"), config.answers_file);
+    }
+
+    #[test]
+    fn test_find() {
+        let root = std::env::temp_dir().join(format!("aoc22-config-{}", std::process::id()));
+        let nested = root.join("src/days");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(root.join(FILE_NAME), "").unwrap();
+
+        assert_eq!(Some(root.join(FILE_NAME)), find(&nested));
+        assert_eq!(Some(root.join(FILE_NAME)), find(&root));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(Config::parse("format = \"yaml\"", Path::new(".")).is_err());
+        assert!(Config::parse("data_dir = \"inputs\"", Path::new(".")).is_err());
+    }
+
+    #[test]
+    fn test_with_options() {
+        let config = Config::parse("format = \"json\"\n[timeouts]\nday08 = 60", Path::new("."))
+            .unwrap()
+            .with_options(&Options::parse(["--format", "text", "--timeout", "5"]).unwrap());
+
+        assert_eq!(Format::Text, config.format);
+        assert_eq!(Some(Duration::from_secs(5)), config.timeout(8));
+    }
+}
@@ -0,0 +1,823 @@
+use crate::output::Format;
+use anyhow::{anyhow, Context, Error, Result};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::io::BufRead;
+use std::str::FromStr;
+
+/// An elf's calorie total. Elves are numbered from 1, in the order they appear in the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Total {
+    pub elf: usize,
+    pub calories: u32,
+}
+
+pub fn part_one(input: &str) -> Result<u32> {
+    let top = top_k(input.as_bytes(), 1)?;
+    top.first().map(|t| t.calories).context("no elves in input")
+}
+
+pub fn part_two(input: &str) -> Result<u32> {
+    let top = top_k(input.as_bytes(), 3)?;
+    top.iter().try_fold(0_u32, |sum, t| {
+        sum.checked_add(t.calories)
+            .context("calorie total of the top three overflows")
+    })
+}
+
+/// Finds the `k` elves carrying the most calories, most first, reading the input line by line.
+///
+/// Only the `k` best totals seen so far are kept, in a min-heap, so memory doesn't grow with the
+/// input. Ties go to the elf that came first.
+pub fn top_k(mut reader: impl BufRead, k: usize) -> Result<Vec<Total>> {
+    if k == 0 {
+        return Err(anyhow!("need to keep at least one elf"));
+    }
+
+    // Smallest calories on top, and among equal calories the latest elf
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+    let mut keep = |total: Total| {
+        heap.push(Reverse((total.calories, Reverse(total.elf))));
+        if heap.len() > k {
+            heap.pop();
+        }
+    };
+
+    let mut line = String::new();
+    let mut elves = 0;
+    let mut current: Option<u32> = None;
+    loop {
+        line.clear();
+        let read = reader
+            .read_line(&mut line)
+            .context("could not read calories")?;
+        let snack = line.trim_end_matches('\n').trim_end_matches('\r');
+
+        if snack.is_empty() {
+            if let Some(calories) = current.take() {
+                elves += 1;
+                keep(Total {
+                    elf: elves,
+                    calories,
+                });
+            }
+            if read == 0 {
+                break;
+            }
+            continue;
+        }
+
+        let (_, total) = add_snack(current.unwrap_or(0), snack)?;
+        current = Some(total);
+    }
+
+    let mut top: Vec<_> = heap
+        .into_iter()
+        .map(|Reverse((calories, Reverse(elf)))| Total { elf, calories })
+        .collect();
+    top.sort_by_key(|t| (Reverse(t.calories), t.elf));
+    Ok(top)
+}
+
+// Parses a snack and adds it to an elf's running total, returning both
+fn add_snack(total: u32, snack: &str) -> Result<(u32, u32)> {
+    let cals: u32 = snack
+        .parse()
+        .context(format!("'{}' is not a valid snack", snack))?;
+    let total = total
+        .checked_add(cals)
+        .context(format!("calorie total overflows at snack '{}'", snack))?;
+    Ok((cals, total))
+}
+
+pub fn render_top(top: &[Total], format: Format) -> Result<String> {
+    match format {
+        Format::Text => Ok(top
+            .iter()
+            .enumerate()
+            .map(|(rank, t)| format!("{:>3}) elf {:<6} {:>8}", rank + 1, t.elf, t.calories))
+            .collect::<Vec<_>>()
+            .join("\n")),
+        Format::Json => {
+            let top: Vec<_> = top
+                .iter()
+                .enumerate()
+                .map(|(rank, t)| {
+                    serde_json::json!({
+                        "rank": rank + 1,
+                        "elf": t.elf,
+                        "calories": t.calories,
+                    })
+                })
+                .collect();
+            Ok(serde_json::to_string(&top)?)
+        }
+    }
+}
+
+/// Every elf and what they carry. Runs of blank lines separate elves the same as a single one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Inventory {
+    pub elves: Vec<Elf>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Elf {
+    /// Position in the input, counting from 1.
+    pub number: usize,
+    /// Calories of each snack, in input order. Never empty.
+    snacks: Vec<u32>,
+    total: u32,
+}
+
+impl Elf {
+    pub fn snacks(&self) -> &[u32] {
+        &self.snacks
+    }
+
+    pub fn total(&self) -> u32 {
+        self.total
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.total as f64 / self.snacks.len() as f64
+    }
+
+    pub fn median(&self) -> f64 {
+        let mut snacks = self.snacks.clone();
+        snacks.sort_unstable();
+        let mid = snacks.len() / 2;
+        if snacks.len().is_multiple_of(2) {
+            (snacks[mid - 1] as f64 + snacks[mid] as f64) / 2.0
+        } else {
+            snacks[mid] as f64
+        }
+    }
+
+    /// Population standard deviation of the snacks.
+    pub fn stddev(&self) -> f64 {
+        let mean = self.mean();
+        let variance = self
+            .snacks
+            .iter()
+            .map(|&cals| (cals as f64 - mean).powi(2))
+            .sum::<f64>()
+            / self.snacks.len() as f64;
+        variance.sqrt()
+    }
+}
+
+/// Elves whose totals fall in `from..=to`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bucket {
+    pub from: u32,
+    pub to: u32,
+    pub count: usize,
+}
+
+impl Inventory {
+    /// Elves from most to fewest calories, ties in input order.
+    pub fn ranked(&self) -> Vec<&Elf> {
+        let mut ranked: Vec<_> = self.elves.iter().collect();
+        ranked.sort_by_key(|elf| (Reverse(elf.total), elf.number));
+        ranked
+    }
+
+    /// Elves carrying more than `calories`, ranked.
+    pub fn heavier_than(&self, calories: u32) -> Vec<&Elf> {
+        self.ranked()
+            .into_iter()
+            .filter(|elf| elf.total > calories)
+            .collect()
+    }
+
+    /// Splits the range of totals into `buckets` equal slices and counts the elves in each.
+    pub fn histogram(&self, buckets: usize) -> Vec<Bucket> {
+        let (Some(min), Some(max)) = (
+            self.elves.iter().map(Elf::total).min(),
+            self.elves.iter().map(Elf::total).max(),
+        ) else {
+            return vec![];
+        };
+        let (min, max) = (min as u64, max as u64);
+        let width = (max - min) / buckets.max(1) as u64 + 1;
+
+        let mut histogram: Vec<_> = (0..buckets.max(1) as u64)
+            .map(|i| min + i * width)
+            .take_while(|&from| from <= max)
+            .map(|from| Bucket {
+                from: from as u32,
+                to: (from + width - 1).min(max) as u32,
+                count: 0,
+            })
+            .collect();
+        for elf in self.elves.iter() {
+            histogram[((elf.total as u64 - min) / width) as usize].count += 1;
+        }
+        histogram
+    }
+
+    /// Per-elf statistics for the ranked elves carrying more than `over` calories (all of them if
+    /// unset), then a histogram of every elf's total.
+    pub fn report(&self, over: Option<u32>, format: Format) -> Result<String> {
+        let ranked: Vec<_> = self.ranked().into_iter().enumerate().collect();
+        let rows = ranked
+            .into_iter()
+            .filter(|(_, elf)| over.is_none_or(|over| elf.total > over));
+        let histogram = self.histogram(HISTOGRAM_BUCKETS);
+
+        match format {
+            Format::Text => {
+                let mut lines = vec![format!(
+                    "{:>4} {:>6} {:>6} {:>8} {:>9} {:>9} {:>9}",
+                    "rank", "elf", "snacks", "total", "mean", "median", "stddev"
+                )];
+                for (rank, elf) in rows {
+                    lines.push(format!(
+                        "{:>4} {:>6} {:>6} {:>8} {:>9.1} {:>9.1} {:>9.1}",
+                        rank + 1,
+                        elf.number,
+                        elf.snacks().len(),
+                        elf.total,
+                        elf.mean(),
+                        elf.median(),
+                        elf.stddev()
+                    ));
+                }
+
+                lines.push(String::new());
+                lines.push("Totals".into());
+                let most = histogram.iter().map(|b| b.count).max().unwrap_or(0);
+                for bucket in histogram.iter() {
+                    let bar = (bucket.count * HISTOGRAM_WIDTH).div_ceil(most.max(1));
+                    let line = format!(
+                        "{:>8} - {:<8} {:>5} {}",
+                        bucket.from,
+                        bucket.to,
+                        bucket.count,
+                        "#".repeat(bar)
+                    );
+                    lines.push(line.trim_end().to_string());
+                }
+                Ok(lines.join("\n"))
+            }
+            Format::Json => {
+                let elves: Vec<_> = rows
+                    .map(|(rank, elf)| {
+                        serde_json::json!({
+                            "rank": rank + 1,
+                            "elf": elf.number,
+                            "snacks": elf.snacks().len(),
+                            "total": elf.total,
+                            "mean": elf.mean(),
+                            "median": elf.median(),
+                            "stddev": elf.stddev(),
+                        })
+                    })
+                    .collect();
+                let histogram: Vec<_> = histogram
+                    .iter()
+                    .map(|b| serde_json::json!({"from": b.from, "to": b.to, "count": b.count}))
+                    .collect();
+                Ok(serde_json::to_string(
+                    &serde_json::json!({"elves": elves, "histogram": histogram}),
+                )?)
+            }
+        }
+    }
+}
+
+const HISTOGRAM_BUCKETS: usize = 10;
+const HISTOGRAM_WIDTH: usize = 40;
+
+impl FromStr for Inventory {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<_> = s.lines().collect();
+        let mut elves = vec![];
+
+        for group in lines.split(|l| l.is_empty()).filter(|g| !g.is_empty()) {
+            let mut snacks = vec![];
+            let mut total = 0;
+            for snack in group {
+                let (cals, sum) = add_snack(total, snack)?;
+                snacks.push(cals);
+                total = sum;
+            }
+            elves.push(Elf {
+                number: elves.len() + 1,
+                snacks,
+                total,
+            });
+        }
+
+        Ok(Self { elves })
+    }
+}
+
+/// Redistributing the snacks so the heaviest elf carries as little as possible.
+///
+/// This is multiprocessor scheduling: snacks are jobs that can't be split, elves are machines, and
+/// the heaviest load is the makespan. It's NP-hard, so the exact search is only practical for a
+/// few dozen snacks; past that the heuristic gets within a hair of the lower bound.
+pub mod rebalance {
+    use super::{Elf, Inventory};
+    use crate::output::Format;
+    use anyhow::{anyhow, Error, Result};
+    use std::cmp::Reverse;
+    use std::collections::BinaryHeap;
+    use std::str::FromStr;
+
+    /// Above this many snacks, [`Method::Auto`] falls back to the heuristic.
+    pub const EXACT_LIMIT: usize = 32;
+    /// Nodes the exact search visits before settling for the best plan found so far.
+    pub const NODE_LIMIT: u64 = 20_000_000;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum Method {
+        /// Branch and bound, seeded with the heuristic's plan.
+        Exact,
+        /// Longest snack first onto the lightest elf, then improving moves and swaps.
+        Heuristic,
+        #[default]
+        Auto,
+    }
+
+    impl FromStr for Method {
+        type Err = Error;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "exact" => Ok(Method::Exact),
+                "heuristic" => Ok(Method::Heuristic),
+                "auto" => Ok(Method::Auto),
+                _ => Err(anyhow!(
+                    "'{}' is not a rebalancing method (exact, heuristic or auto)",
+                    s
+                )),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Plan {
+        /// The snacks each elf ends up with, in elf order.
+        pub loads: Vec<Vec<u32>>,
+        pub heaviest: u64,
+        /// No plan can do better than this: the largest snack, or the average load rounded up.
+        pub lower_bound: u64,
+        /// Whether `heaviest` is known to be the best possible.
+        pub optimal: bool,
+        pub method: Method,
+    }
+
+    /// Rebalances the snacks of every elf in `inventory` among the same number of elves.
+    pub fn rebalance(inventory: &Inventory, method: Method) -> Result<Plan> {
+        let mut snacks: Vec<u32> = inventory
+            .elves
+            .iter()
+            .flat_map(|elf| elf.snacks().iter().copied())
+            .collect();
+        if snacks.is_empty() {
+            return Err(anyhow!("no snacks to rebalance"));
+        }
+        snacks.sort_unstable_by_key(|&cals| Reverse(cals));
+
+        let elves = inventory.elves.len();
+        let lower_bound = lower_bound(&snacks, elves);
+        let method = match method {
+            Method::Auto if snacks.len() <= EXACT_LIMIT => Method::Exact,
+            Method::Auto => Method::Heuristic,
+            method => method,
+        };
+
+        let mut loads = improve(lpt(&snacks, elves));
+        let mut optimal = heaviest(&loads) == lower_bound;
+        if method == Method::Exact && !optimal {
+            let mut search = Search::new(&snacks, elves, loads.clone(), lower_bound);
+            search.run(0);
+            optimal = search.nodes <= NODE_LIMIT;
+            loads = search.best;
+        }
+
+        Ok(Plan {
+            heaviest: heaviest(&loads),
+            loads,
+            lower_bound,
+            optimal,
+            method,
+        })
+    }
+
+    fn lower_bound(snacks: &[u32], elves: usize) -> u64 {
+        let total: u64 = snacks.iter().map(|&cals| cals as u64).sum();
+        let largest = snacks.iter().copied().max().unwrap_or(0) as u64;
+        largest.max(total.div_ceil(elves as u64))
+    }
+
+    fn load(snacks: &[u32]) -> u64 {
+        snacks.iter().map(|&cals| cals as u64).sum()
+    }
+
+    fn heaviest(loads: &[Vec<u32>]) -> u64 {
+        loads.iter().map(|snacks| load(snacks)).max().unwrap_or(0)
+    }
+
+    // Each snack, largest first, goes to whichever elf is carrying the least so far
+    fn lpt(snacks: &[u32], elves: usize) -> Vec<Vec<u32>> {
+        let mut loads = vec![vec![]; elves];
+        let mut lightest: BinaryHeap<_> = (0..elves).map(|elf| Reverse((0_u64, elf))).collect();
+
+        for &cals in snacks {
+            let Reverse((total, elf)) = lightest.pop().unwrap();
+            loads[elf].push(cals);
+            lightest.push(Reverse((total + cals as u64, elf)));
+        }
+        loads
+    }
+
+    // Takes load off the heaviest elf, by moving one of its snacks elsewhere or swapping it for a
+    // smaller one, for as long as that leaves both elves lighter than it was. Each step lowers the
+    // heaviest load or the number of elves carrying it, so this terminates.
+    fn improve(mut loads: Vec<Vec<u32>>) -> Vec<Vec<u32>> {
+        let mut totals: Vec<u64> = loads.iter().map(|snacks| load(snacks)).collect();
+
+        'improve: loop {
+            let (heavy, &max) = totals
+                .iter()
+                .enumerate()
+                .max_by_key(|&(elf, &total)| (total, Reverse(elf)))
+                .unwrap();
+
+            for i in 0..loads[heavy].len() {
+                let cals = loads[heavy][i] as u64;
+                for other in (0..loads.len()).filter(|&other| other != heavy) {
+                    if totals[other] + cals < max {
+                        let snack = loads[heavy].swap_remove(i);
+                        loads[other].push(snack);
+                        totals[heavy] -= cals;
+                        totals[other] += cals;
+                        continue 'improve;
+                    }
+
+                    for j in 0..loads[other].len() {
+                        let smaller = loads[other][j] as u64;
+                        if smaller < cals && totals[other] + cals - smaller < max {
+                            loads[other][j] = loads[heavy][i];
+                            loads[heavy][i] = smaller as u32;
+                            totals[heavy] = totals[heavy] - cals + smaller;
+                            totals[other] = totals[other] + cals - smaller;
+                            continue 'improve;
+                        }
+                    }
+                }
+            }
+            return loads;
+        }
+    }
+
+    // Depth-first over which elf gets each snack, largest snacks first, pruning any branch that
+    // can't beat the best plan so far
+    struct Search<'a> {
+        snacks: &'a [u32],
+        lower_bound: u64,
+        totals: Vec<u64>,
+        current: Vec<Vec<u32>>,
+        best: Vec<Vec<u32>>,
+        best_heaviest: u64,
+        nodes: u64,
+    }
+
+    impl<'a> Search<'a> {
+        fn new(snacks: &'a [u32], elves: usize, best: Vec<Vec<u32>>, lower_bound: u64) -> Self {
+            Self {
+                snacks,
+                lower_bound,
+                totals: vec![0; elves],
+                current: vec![vec![]; elves],
+                best_heaviest: heaviest(&best),
+                best,
+                nodes: 0,
+            }
+        }
+
+        fn run(&mut self, idx: usize) {
+            self.nodes += 1;
+            if self.best_heaviest == self.lower_bound || self.nodes > NODE_LIMIT {
+                return;
+            }
+            let Some(&cals) = self.snacks.get(idx) else {
+                self.best_heaviest = self.totals.iter().copied().max().unwrap_or(0);
+                self.best = self.current.clone();
+                return;
+            };
+
+            // Elves carrying the same amount are interchangeable, so only try one of them
+            let mut tried = vec![];
+            for elf in 0..self.totals.len() {
+                let total = self.totals[elf];
+                if total + cals as u64 >= self.best_heaviest || tried.contains(&total) {
+                    continue;
+                }
+                tried.push(total);
+
+                self.totals[elf] += cals as u64;
+                self.current[elf].push(cals);
+                self.run(idx + 1);
+                self.current[elf].pop();
+                self.totals[elf] -= cals as u64;
+            }
+        }
+    }
+
+    impl Plan {
+        pub fn render(&self, before: &[Elf], format: Format) -> Result<String> {
+            let was = before.iter().map(Elf::total).max().unwrap_or(0);
+            let gap = self.heaviest - self.lower_bound;
+
+            match format {
+                Format::Text => {
+                    let method = match self.method {
+                        Method::Exact => "exact",
+                        _ => "heuristic",
+                    };
+                    let verdict = if self.optimal {
+                        "optimal"
+                    } else {
+                        "not proven optimal"
+                    };
+                    let mut lines = vec![
+                        format!(
+                            "heaviest load {} ({}, {}), was {}",
+                            self.heaviest, method, verdict, was
+                        ),
+                        // Only snacks of 0 calories give a bound of 0, and then nothing is over
+                        if self.lower_bound == 0 {
+                            "lower bound 0, 0 over".to_string()
+                        } else {
+                            format!(
+                                "lower bound {}, {} over ({:.2}%)",
+                                self.lower_bound,
+                                gap,
+                                100.0 * gap as f64 / self.lower_bound as f64
+                            )
+                        },
+                        String::new(),
+                        format!("{:>6} {:>8}  snacks", "elf", "total"),
+                    ];
+                    for (elf, snacks) in self.loads.iter().enumerate() {
+                        let snacks: Vec<_> = snacks.iter().map(u32::to_string).collect();
+                        lines.push(format!(
+                            "{:>6} {:>8}  {}",
+                            elf + 1,
+                            load(self.loads[elf].as_slice()),
+                            snacks.join(" ")
+                        ));
+                    }
+                    Ok(lines.join("\n"))
+                }
+                Format::Json => {
+                    let elves: Vec<_> = self
+                        .loads
+                        .iter()
+                        .enumerate()
+                        .map(|(elf, snacks)| {
+                            serde_json::json!({
+                                "elf": elf + 1,
+                                "total": load(snacks),
+                                "snacks": snacks,
+                            })
+                        })
+                        .collect();
+                    Ok(serde_json::to_string(&serde_json::json!({
+                        "method": match self.method {
+                            Method::Exact => "exact",
+                            _ => "heuristic",
+                        },
+                        "heaviest": self.heaviest,
+                        "lower_bound": self.lower_bound,
+                        "optimal": self.optimal,
+                        "before": was,
+                        "elves": elves,
+                    }))?)
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn inventory(elves: &[&[u32]]) -> Inventory {
+            let input: Vec<_> = elves
+                .iter()
+                .map(|snacks| {
+                    snacks
+                        .iter()
+                        .map(u32::to_string)
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                })
+                .collect();
+            input.join("\n\n").parse().unwrap()
+        }
+
+        fn check(plan: &Plan, inventory: &Inventory) {
+            let mut before: Vec<_> = inventory
+                .elves
+                .iter()
+                .flat_map(|elf| elf.snacks().to_vec())
+                .collect();
+            let mut after: Vec<_> = plan.loads.iter().flatten().copied().collect();
+            before.sort();
+            after.sort();
+            assert_eq!(before, after);
+            assert_eq!(inventory.elves.len(), plan.loads.len());
+            assert_eq!(heaviest(&plan.loads), plan.heaviest);
+            assert!(plan.heaviest >= plan.lower_bound);
+        }
+
+        #[test]
+        fn test_example() {
+            let inventory: Inventory = include_str!("../../data/day01-ex.txt").parse().unwrap();
+
+            let plan = rebalance(&inventory, Method::Auto).unwrap();
+            check(&plan, &inventory);
+            assert_eq!(Method::Exact, plan.method);
+            assert_eq!(
+                (11000, 11000, true),
+                (plan.heaviest, plan.lower_bound, plan.optimal)
+            );
+        }
+
+        #[test]
+        fn test_exact_beats_lpt() {
+            // LPT leaves one elf with 3+2+2 = 7, but 3+3 and 2+2+2 make 6 each
+            let inventory = inventory(&[&[3, 3, 2], &[2, 2]]);
+            assert_eq!(7, heaviest(&lpt(&[3, 3, 2, 2, 2], 2)));
+
+            let plan = rebalance(&inventory, Method::Exact).unwrap();
+            check(&plan, &inventory);
+            assert_eq!((6, true), (plan.heaviest, plan.optimal));
+
+            let plan = rebalance(&inventory, Method::Heuristic).unwrap();
+            check(&plan, &inventory);
+            assert_eq!(6, plan.heaviest);
+        }
+
+        #[test]
+        fn test_exact_above_lower_bound() {
+            // Three elves and four snacks of 5: someone has to carry two, though the average is 7
+            let inventory = inventory(&[&[5, 5], &[5], &[5]]);
+
+            let plan = rebalance(&inventory, Method::Exact).unwrap();
+            check(&plan, &inventory);
+            assert_eq!(
+                (10, 7, true),
+                (plan.heaviest, plan.lower_bound, plan.optimal)
+            );
+        }
+
+        #[test]
+        fn test_heuristic_many_snacks() {
+            // 200 elves of 1 to 15 snacks each, pseudo-randomly sized like a puzzle input
+            let mut seed = 12345_u64;
+            let mut next = |range: u64| {
+                seed = seed
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (seed >> 33) % range
+            };
+            let elves: Vec<Vec<u32>> = (0..200)
+                .map(|_| (0..=next(15)).map(|_| 1000 + next(9000) as u32).collect())
+                .collect();
+            let elves: Vec<&[u32]> = elves.iter().map(Vec::as_slice).collect();
+            let inventory = inventory(&elves);
+
+            let plan = rebalance(&inventory, Method::Auto).unwrap();
+            check(&plan, &inventory);
+            assert_eq!(Method::Heuristic, plan.method);
+            assert!(plan.heaviest - plan.lower_bound <= plan.lower_bound / 100);
+        }
+
+        #[test]
+        fn test_zero_calories() {
+            let inventory = inventory(&[&[0, 0], &[0]]);
+            let plan = rebalance(&inventory, Method::Auto).unwrap();
+            let text = plan.render(&inventory.elves, Format::Text).unwrap();
+            assert!(text.contains("lower bound 0, 0 over\n"), "{}", text);
+        }
+
+        #[test]
+        fn test_method() {
+            assert_eq!(Method::Exact, "exact".parse().unwrap());
+            assert!("fast".parse::<Method>().is_err());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::{self, assert_snapshot};
+
+    #[test]
+    fn test_error_snapshot() {
+        let outcomes = [
+            ("1000\n2000\n\nabc\n", part_one("1000\n2000\n\nabc\n")),
+            ("1000\n-2000\n", part_one("1000\n-2000\n")),
+            ("4294967295\n1\n", part_two("4294967295\n1\n")),
+            ("4294967295\n\n2\n\n3\n", part_two("4294967295\n\n2\n\n3\n")),
+        ];
+
+        assert_snapshot("day01-errors", &snapshot::outcomes(outcomes));
+    }
+
+    #[test]
+    fn test_top_k() {
+        let input = include_str!("../../data/day01-ex.txt");
+
+        let top = top_k(input.as_bytes(), 3).unwrap();
+        let elves: Vec<_> = top.iter().map(|t| (t.elf, t.calories)).collect();
+        assert_eq!(vec![(4, 24000), (3, 11000), (5, 10000)], elves);
+
+        assert_eq!(5, top_k(input.as_bytes(), 10).unwrap().len());
+        assert!(top_k(input.as_bytes(), 0).is_err());
+    }
+
+    #[test]
+    fn test_top_k_ties() {
+        let top = top_k("5\n\n7\n\n\n5\r\n\r\n2\n3\n".as_bytes(), 2).unwrap();
+        assert_eq!(
+            vec![
+                Total {
+                    elf: 2,
+                    calories: 7
+                },
+                Total {
+                    elf: 1,
+                    calories: 5
+                }
+            ],
+            top
+        );
+    }
+
+    #[test]
+    fn test_inventory() {
+        let inventory: Inventory = include_str!("../../data/day01-ex.txt").parse().unwrap();
+        assert_eq!(5, inventory.elves.len());
+
+        let elf = &inventory.elves[3];
+        assert_eq!((4, 24000), (elf.number, elf.total()));
+        assert_eq!(8000.0, elf.mean());
+        assert_eq!(8000.0, elf.median());
+        assert!((elf.stddev() - 816.5).abs() < 0.1);
+        assert_eq!(5500.0, inventory.elves[2].median());
+
+        let heavy: Vec<_> = inventory
+            .heavier_than(10000)
+            .iter()
+            .map(|e| e.number)
+            .collect();
+        assert_eq!(vec![4, 3], heavy);
+    }
+
+    #[test]
+    fn test_histogram() {
+        let inventory: Inventory = include_str!("../../data/day01-ex.txt").parse().unwrap();
+        let histogram = inventory.histogram(4);
+
+        assert_eq!(4, histogram.len());
+        assert_eq!(
+            (4000, 9000, 2),
+            (histogram[0].from, histogram[0].to, histogram[0].count)
+        );
+        assert_eq!(
+            (19003, 24000, 1),
+            (histogram[3].from, histogram[3].to, histogram[3].count)
+        );
+        assert_eq!(5, histogram.iter().map(|b| b.count).sum::<usize>());
+
+        let inventory: Inventory = "7\n\n7\n".parse().unwrap();
+        assert_eq!(
+            vec![Bucket {
+                from: 7,
+                to: 7,
+                count: 2
+            }],
+            inventory.histogram(10)
+        );
+    }
+
+    #[test]
+    fn test_report_snapshot() {
+        let inventory: Inventory = include_str!("../../data/day01-ex.txt").parse().unwrap();
+        assert_snapshot(
+            "day01-report",
+            &inventory.report(Some(5000), Format::Text).unwrap(),
+        );
+    }
+}
@@ -0,0 +1,1077 @@
+use anyhow::{anyhow, Context, Error, Result};
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::Path;
+use std::str::FromStr;
+
+pub fn part_one(input: &str) -> Result<u32> {
+    let game = Game::default();
+    input.lines().try_fold(0, |total, round| {
+        add_score(total, game.score_strategy_one(round)?)
+    })
+}
+
+pub fn part_two(input: &str) -> Result<u32> {
+    let game = Game::default();
+    input.lines().try_fold(0, |total, round| {
+        add_score(total, game.score_strategy_two(round)?)
+    })
+}
+
+fn add_score(total: u32, score: u32) -> Result<u32> {
+    total.checked_add(score).context("total score overflows")
+}
+
+/// [`part_one`], scoring well-formed rounds straight from a lookup table.
+pub fn part_one_bytes(input: &str) -> Result<u32> {
+    score_bytes(input, &TABLES.0, Game::score_strategy_one)
+}
+
+/// [`part_two`], scoring well-formed rounds straight from a lookup table.
+pub fn part_two_bytes(input: &str) -> Result<u32> {
+    score_bytes(input, &TABLES.1, Game::score_strategy_two)
+}
+
+type Table = [[u32; 3]; 3];
+
+lazy_static! {
+    // Scores of every round of the puzzle's game, by opponent and player symbol, for each strategy
+    static ref TABLES: (Table, Table) = {
+        let game = Game::default();
+        let mut tables = ([[0; 3]; 3], [[0; 3]; 3]);
+        for (you, opponent) in ["A", "B", "C"].iter().enumerate() {
+            for (me, player) in ["X", "Y", "Z"].iter().enumerate() {
+                let round = format!("{} {}", opponent, player);
+                tables.0[you][me] = game.score_strategy_one(&round).unwrap();
+                tables.1[you][me] = game.score_strategy_two(&round).unwrap();
+            }
+        }
+        tables
+    };
+}
+
+// Rounds that aren't exactly `[ABC] [XYZ]` go through the checked parser, which scores them the
+// same way or explains what's wrong with them
+fn score_bytes(input: &str, table: &Table, checked: fn(&Game, &str) -> Result<u32>) -> Result<u32> {
+    let mut total = 0;
+    for round in input.lines() {
+        let score = match *round.as_bytes() {
+            [you @ b'A'..=b'C', b' ', me @ b'X'..=b'Z'] => {
+                table[(you - b'A') as usize][(me - b'X') as usize]
+            }
+            _ => checked(&Game::default(), round)?,
+        };
+        total = add_score(total, score)?;
+    }
+    Ok(total)
+}
+
+/// A shape, by its position in the game's cyclic order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Shape(pub usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    pub const ALL: [Outcome; 3] = [Outcome::Lose, Outcome::Draw, Outcome::Win];
+}
+
+/// A game of N shapes in a cyclic dominance relation, like rock-paper-scissors or
+/// rock-paper-scissors-lizard-Spock.
+///
+/// Shapes are listed so that each one beats the (N - 1) / 2 shapes just before it, wrapping round
+/// at the start: rock, paper, scissors has paper beating rock and rock beating scissors. N must be
+/// odd so that every pair of shapes has a winner.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Game {
+    #[serde(rename = "shape")]
+    shapes: Vec<ShapeDef>,
+    outcomes: Outcomes,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ShapeDef {
+    name: String,
+    score: u32,
+    /// Symbol for this shape in the opponent's column.
+    opponent: String,
+    /// Symbol for this shape in the player's column, under the first strategy.
+    player: String,
+}
+
+/// Symbols for the player's column under the second strategy, and the score for each outcome.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Outcomes {
+    lose: OutcomeDef,
+    draw: OutcomeDef,
+    win: OutcomeDef,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OutcomeDef {
+    symbol: String,
+    score: u32,
+}
+
+impl Default for Game {
+    /// Plain rock-paper-scissors, scored as in the puzzle.
+    fn default() -> Self {
+        let shape = |name: &str, score, opponent: &str, player: &str| ShapeDef {
+            name: name.into(),
+            score,
+            opponent: opponent.into(),
+            player: player.into(),
+        };
+        let outcome = |symbol: &str, score| OutcomeDef {
+            symbol: symbol.into(),
+            score,
+        };
+
+        Self {
+            shapes: vec![
+                shape("Rock", 1, "A", "X"),
+                shape("Paper", 2, "B", "Y"),
+                shape("Scissors", 3, "C", "Z"),
+            ],
+            outcomes: Outcomes {
+                lose: outcome("X", 0),
+                draw: outcome("Y", 3),
+                win: outcome("Z", 6),
+            },
+        }
+    }
+}
+
+impl Game {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .context(format!("could not read '{}'", path.display()))?;
+        contents
+            .parse()
+            .context(format!("invalid game '{}'", path.display()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.shapes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shapes.is_empty()
+    }
+
+    pub fn shapes(&self) -> impl Iterator<Item = Shape> {
+        (0..self.len()).map(Shape)
+    }
+
+    pub fn name(&self, shape: Shape) -> &str {
+        &self.shapes[shape.0].name
+    }
+
+    pub fn shape_score(&self, shape: Shape) -> u32 {
+        self.shapes[shape.0].score
+    }
+
+    pub fn outcome_score(&self, outcome: Outcome) -> u32 {
+        self.outcome_def(outcome).score
+    }
+
+    /// Symbols of the player's column, in shape order for the first strategy and in outcome
+    /// order (lose, draw, win) for the second.
+    pub fn player_symbols(&self) -> (Vec<&str>, Vec<&str>) {
+        let shapes = self.shapes.iter().map(|s| s.player.as_str()).collect();
+        let outcomes = Outcome::ALL
+            .iter()
+            .map(|&o| self.outcome_def(o).symbol.as_str())
+            .collect();
+        (shapes, outcomes)
+    }
+
+    pub fn beats(&self, shape: Shape, other: Shape) -> bool {
+        let n = self.len();
+        let ahead = (shape.0 + n - other.0) % n;
+        (1..=n / 2).contains(&ahead)
+    }
+
+    /// How `me` fares against `you`.
+    pub fn outcome(&self, me: Shape, you: Shape) -> Outcome {
+        if self.beats(me, you) {
+            Outcome::Win
+        } else if self.beats(you, me) {
+            Outcome::Lose
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    /// The shape to play against `you` for the given outcome. With more than three shapes there
+    /// can be several; this picks the one scoring most, and the first of those on a tie.
+    pub fn find_shape(&self, you: Shape, outcome: Outcome) -> Shape {
+        self.shapes()
+            .filter(|&me| self.outcome(me, you) == outcome)
+            .min_by_key(|&me| (std::cmp::Reverse(self.shape_score(me)), me))
+            .expect("every shape has a shape to win, lose and draw against")
+    }
+
+    pub fn score(&self, me: Shape, you: Shape) -> u32 {
+        self.outcome_score(self.outcome(me, you)) + self.shape_score(me)
+    }
+
+    pub fn opponent(&self, symbol: &str) -> Result<Shape> {
+        self.shapes
+            .iter()
+            .position(|s| s.opponent == symbol)
+            .map(Shape)
+            .ok_or_else(|| anyhow!("'{}' is not a valid hand", symbol))
+    }
+
+    pub fn player(&self, symbol: &str) -> Result<Shape> {
+        self.shapes
+            .iter()
+            .position(|s| s.player == symbol)
+            .map(Shape)
+            .ok_or_else(|| anyhow!("'{}' is not a valid hand", symbol))
+    }
+
+    pub fn player_outcome(&self, symbol: &str) -> Result<Outcome> {
+        Outcome::ALL
+            .into_iter()
+            .find(|&o| self.outcome_def(o).symbol == symbol)
+            .ok_or_else(|| anyhow!("'{}' is not a valid outcome", symbol))
+    }
+
+    /// Scores a round where the second column is the shape to play.
+    pub fn score_strategy_one(&self, round: &str) -> Result<u32> {
+        let (you, me) = split_round(round)?;
+        let you = self.opponent(you)?;
+        let me = self.player(me)?;
+        Ok(self.score(me, you))
+    }
+
+    /// Scores a round where the second column is the outcome to aim for.
+    pub fn score_strategy_two(&self, round: &str) -> Result<u32> {
+        let (you, outcome) = split_round(round)?;
+        let you = self.opponent(you)?;
+        let outcome = self.player_outcome(outcome)?;
+        Ok(self.score(self.find_shape(you, outcome), you))
+    }
+
+    fn outcome_def(&self, outcome: Outcome) -> &OutcomeDef {
+        match outcome {
+            Outcome::Lose => &self.outcomes.lose,
+            Outcome::Draw => &self.outcomes.draw,
+            Outcome::Win => &self.outcomes.win,
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        let n = self.len();
+        if n < 3 || n.is_multiple_of(2) {
+            return Err(anyhow!(
+                "a game needs an odd number of shapes, at least 3 (got {})",
+                n
+            ));
+        }
+
+        let (player, outcomes) = self.player_symbols();
+        let columns = [
+            (
+                "name",
+                self.shapes.iter().map(|s| s.name.as_str()).collect(),
+            ),
+            (
+                "opponent symbol",
+                self.shapes.iter().map(|s| s.opponent.as_str()).collect(),
+            ),
+            ("player symbol", player),
+            ("outcome symbol", outcomes),
+        ];
+        for (what, values) in columns {
+            let mut seen = HashSet::new();
+            for value in values {
+                if value.is_empty() || value.contains(char::is_whitespace) {
+                    return Err(anyhow!(
+                        "{} '{}' can't be blank or contain spaces",
+                        what,
+                        value
+                    ));
+                }
+                if !seen.insert(value) {
+                    return Err(anyhow!("duplicate {} '{}'", what, value));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Game {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let game: Game = toml::from_str(s)?;
+        game.validate()?;
+        Ok(game)
+    }
+}
+
+fn split_round(round: &str) -> Result<(&str, &str)> {
+    round
+        .split_once(' ')
+        .context(format!("'{}' is not a valid round", round))
+}
+
+/// Working out what the player's column of a guide means, by scoring it under every reading.
+///
+/// The first strategy reads the column as shapes and the second as outcomes, so the candidate
+/// mappings are the permutations of the game's player symbols onto its shapes (N! of them) and of
+/// its outcome symbols onto the three outcomes.
+pub mod mapping {
+    use super::{split_round, Game, Outcome, Shape};
+    use crate::output::Format;
+    use anyhow::{anyhow, Result};
+
+    /// A mapping, indexed by the position of each symbol, and the total it gives the guide.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Scored<T> {
+        pub mapping: Vec<T>,
+        pub total: u64,
+    }
+
+    /// The best and worst mappings of one strategy's symbols, and those giving the target total if
+    /// there is one. Ties keep the first best and the last worst in the order the permutations are
+    /// generated in.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Analysis<T> {
+        pub symbols: Vec<String>,
+        /// How many mappings were scored.
+        pub mappings: u64,
+        pub best: Scored<T>,
+        pub worst: Scored<T>,
+        pub target: Option<u64>,
+        /// Mappings under which the guide scores exactly `target`, in generation order.
+        pub matching: Vec<Scored<T>>,
+    }
+
+    /// Scores `guide` under every mapping of the player's symbols onto shapes.
+    pub fn shapes(game: &Game, guide: &str, target: Option<u64>) -> Result<Analysis<Shape>> {
+        let (symbols, _) = game.player_symbols();
+        let counts = tally(game, guide, &symbols, "hand")?;
+
+        Ok(Analysis::new(&symbols, game.len(), target, Shape, |perm| {
+            score(&counts, |you, symbol| game.score(Shape(perm[symbol]), you))
+        }))
+    }
+
+    /// Scores `guide` under every mapping of the player's symbols onto outcomes.
+    pub fn outcomes(game: &Game, guide: &str, target: Option<u64>) -> Result<Analysis<Outcome>> {
+        let (_, symbols) = game.player_symbols();
+        let counts = tally(game, guide, &symbols, "outcome")?;
+
+        let outcome = |i: usize| Outcome::ALL[i];
+        Ok(Analysis::new(
+            &symbols,
+            Outcome::ALL.len(),
+            target,
+            outcome,
+            |perm| {
+                score(&counts, |you, symbol| {
+                    game.score(game.find_shape(you, outcome(perm[symbol])), you)
+                })
+            },
+        ))
+    }
+
+    impl<T> Analysis<T> {
+        // Scores every permutation of `n` values as it is generated, keeping only the ones reported
+        fn new(
+            symbols: &[&str],
+            n: usize,
+            target: Option<u64>,
+            value: impl Fn(usize) -> T,
+            total: impl Fn(&[usize]) -> u64,
+        ) -> Self {
+            let scored = |perm: &[usize], total| Scored {
+                mapping: perm.iter().map(|&i| value(i)).collect(),
+                total,
+            };
+            let (mut best, mut worst): (Option<Scored<T>>, Option<Scored<T>>) = (None, None);
+            let (mut mappings, mut matching) = (0, vec![]);
+
+            for_each_permutation(n, |perm| {
+                let total = total(perm);
+                mappings += 1;
+                if best.as_ref().is_none_or(|best| total > best.total) {
+                    best = Some(scored(perm, total));
+                }
+                if worst.as_ref().is_none_or(|worst| total <= worst.total) {
+                    worst = Some(scored(perm, total));
+                }
+                if target == Some(total) {
+                    matching.push(scored(perm, total));
+                }
+            });
+
+            // There is always at least one permutation, if only the empty one
+            Self {
+                symbols: symbols.iter().map(|&s| s.to_string()).collect(),
+                mappings,
+                best: best.unwrap(),
+                worst: worst.unwrap(),
+                target,
+                matching,
+            }
+        }
+
+        fn label(&self, scored: &Scored<T>, name: &impl Fn(&T) -> String) -> String {
+            self.symbols
+                .iter()
+                .zip(scored.mapping.iter())
+                .map(|(symbol, value)| format!("{}={}", symbol, name(value)))
+                .collect::<Vec<_>>()
+                .join(" ")
+        }
+
+        fn text(&self, title: &str, name: impl Fn(&T) -> String) -> String {
+            let mut lines = vec![
+                format!("{} ({} mappings)", title, self.mappings),
+                format!(
+                    "  best   {:>8}  {}",
+                    self.best.total,
+                    self.label(&self.best, &name)
+                ),
+                format!(
+                    "  worst  {:>8}  {}",
+                    self.worst.total,
+                    self.label(&self.worst, &name)
+                ),
+            ];
+            if let Some(target) = self.target {
+                lines.push(format!("  giving {}: {}", target, self.matching.len()));
+                for scored in self.matching.iter() {
+                    lines.push(format!("    {}", self.label(scored, &name)));
+                }
+            }
+            lines.join("\n")
+        }
+
+        fn json(&self, name: impl Fn(&T) -> String) -> serde_json::Value {
+            let entry = |scored: &Scored<T>| serde_json::json!({"total": scored.total, "mapping": self.label(scored, &name)});
+            serde_json::json!({
+                "mappings": self.mappings,
+                "best": entry(&self.best),
+                "worst": entry(&self.worst),
+                "matching": self.target.map(|_| self.matching.iter().map(entry).collect::<Vec<_>>()),
+            })
+        }
+    }
+
+    /// Reports the best and worst mappings of `guide` for each strategy, and those giving `target`
+    /// if set. With more than three shapes a guide may only make sense under one strategy; the
+    /// other is reported as not applying.
+    pub fn render(game: &Game, guide: &str, target: Option<u64>, format: Format) -> Result<String> {
+        let (shapes, outcomes) = (shapes(game, guide, target), outcomes(game, guide, target));
+        if let (Err(err), Err(_)) = (&shapes, &outcomes) {
+            return Err(anyhow!("the guide doesn't fit either strategy: {:#}", err));
+        }
+        let shape_name = |&shape: &Shape| game.name(shape).to_string();
+        let outcome_name = |outcome: &Outcome| format!("{:?}", outcome).to_lowercase();
+
+        match format {
+            Format::Text => {
+                let one = match &shapes {
+                    Ok(analysis) => analysis.text("strategy one", shape_name),
+                    Err(err) => format!("strategy one doesn't apply ({:#})", err),
+                };
+                let two = match &outcomes {
+                    Ok(analysis) => analysis.text("strategy two", outcome_name),
+                    Err(err) => format!("strategy two doesn't apply ({:#})", err),
+                };
+                Ok(format!("{}\n\n{}", one, two))
+            }
+            Format::Json => Ok(serde_json::to_string(&serde_json::json!({
+                "one": shapes.ok().map(|a| a.json(shape_name)),
+                "two": outcomes.ok().map(|a| a.json(outcome_name)),
+            }))?),
+        }
+    }
+
+    // Counts the rounds of the guide by opponent shape and player symbol
+    fn tally(game: &Game, guide: &str, symbols: &[&str], what: &str) -> Result<Vec<Vec<u64>>> {
+        let mut counts = vec![vec![0; symbols.len()]; game.len()];
+        for round in guide.lines() {
+            let (you, me) = split_round(round)?;
+            let you = game.opponent(you)?;
+            let me = symbols
+                .iter()
+                .position(|&symbol| symbol == me)
+                .ok_or_else(|| anyhow!("'{}' is not a valid {}", me, what))?;
+            counts[you.0][me] += 1;
+        }
+        Ok(counts)
+    }
+
+    fn score(counts: &[Vec<u64>], round: impl Fn(Shape, usize) -> u32) -> u64 {
+        let mut total = 0;
+        for (you, row) in counts.iter().enumerate() {
+            for (symbol, &count) in row.iter().enumerate() {
+                total += count * round(Shape(you), symbol) as u64;
+            }
+        }
+        total
+    }
+
+    // Visits every ordering of 0..n in lexicographic order, generating each from the last
+    fn for_each_permutation(n: usize, mut visit: impl FnMut(&[usize])) {
+        let mut perm: Vec<_> = (0..n).collect();
+
+        loop {
+            visit(&perm);
+            let Some(i) = (1..n).rev().find(|&i| perm[i - 1] < perm[i]) else {
+                return;
+            };
+            let j = (i..n).rev().find(|&j| perm[i - 1] < perm[j]).unwrap();
+            perm.swap(i - 1, j);
+            perm[i..].reverse();
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_permutations() {
+            let mut all = vec![];
+            for_each_permutation(3, |perm| all.push(perm.to_vec()));
+            assert_eq!(
+                vec![
+                    vec![0, 1, 2],
+                    vec![0, 2, 1],
+                    vec![1, 0, 2],
+                    vec![1, 2, 0],
+                    vec![2, 0, 1],
+                    vec![2, 1, 0]
+                ],
+                all
+            );
+
+            let mut count = 0;
+            for_each_permutation(5, |_| count += 1);
+            assert_eq!(120, count);
+        }
+
+        #[test]
+        fn test_classic_mappings() {
+            let game = Game::default();
+            let guide = include_str!("../../data/day02-ex.txt");
+
+            let analysis = shapes(&game, guide, Some(15)).unwrap();
+            assert_eq!(6, analysis.mappings);
+            let puzzle = vec![Shape(0), Shape(1), Shape(2)];
+            assert!(analysis.matching.iter().any(|s| s.mapping == puzzle));
+
+            let analysis = outcomes(&game, guide, Some(12)).unwrap();
+            let puzzle = Outcome::ALL.to_vec();
+            assert!(analysis.matching.iter().any(|s| s.mapping == puzzle));
+            assert!(analysis.best.total >= 12 && analysis.worst.total <= 12);
+            assert!(outcomes(&game, guide, None).unwrap().matching.is_empty());
+        }
+
+        #[test]
+        fn test_rpsls_mappings() {
+            let game: Game = include_str!("../../data/games/rpsls.toml").parse().unwrap();
+
+            // W is played against both paper and Spock, and lizard (4) beats them both, while V
+            // is best as Spock (5) against rock
+            let analysis = shapes(&game, "A V\nB W\nE W\n", None).unwrap();
+            assert_eq!(120, analysis.mappings);
+            assert_eq!(6 + 5 + 6 + 4 + 6 + 4, analysis.best.total);
+            // The worst loses each round with the lowest scoring loser
+            assert_eq!(3 + 1 + 1, analysis.worst.total);
+
+            assert!(shapes(&game, "A Q\n", None).is_err());
+            assert!(outcomes(&game, "A V\n", None).is_err());
+            assert!(render(&game, "A V\n", None, Format::Text).is_ok());
+            assert!(render(&game, "A Q\n", None, Format::Text).is_err());
+        }
+    }
+}
+
+/// Playing the opponent's column of a guide against strategies that only see past rounds.
+pub mod tournament {
+    use super::{split_round, Game, Outcome, Shape};
+    use crate::collections::FastHashMap;
+    use crate::output::Format;
+    use anyhow::Result;
+
+    /// A round as the guide has it: the opponent's shape, and the shape the guide says to play
+    /// (reading the player's column as shapes, as in part one).
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Round {
+        pub you: Shape,
+        pub guide: Shape,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Played {
+        pub you: Shape,
+        pub me: Shape,
+        pub outcome: Outcome,
+    }
+
+    pub trait Strategy {
+        fn name(&self) -> String;
+
+        /// Picks a shape for the next round, knowing every round played so far and what the
+        /// guide suggests, but not the opponent's next shape.
+        fn choose(&mut self, game: &Game, history: &[Played], guide: Shape) -> Shape;
+    }
+
+    /// Plays whatever the guide says.
+    pub struct Fixed;
+
+    impl Strategy for Fixed {
+        fn name(&self) -> String {
+            "guide".into()
+        }
+
+        fn choose(&mut self, _: &Game, _: &[Played], guide: Shape) -> Shape {
+            guide
+        }
+    }
+
+    /// Beats the opponent's most common shape so far.
+    pub struct Frequency;
+
+    impl Strategy for Frequency {
+        fn name(&self) -> String {
+            "frequency".into()
+        }
+
+        fn choose(&mut self, game: &Game, history: &[Played], _: Shape) -> Shape {
+            let mut counts = vec![0; game.len()];
+            for played in history {
+                counts[played.you.0] += 1;
+            }
+            counter(game, most_common(&counts))
+        }
+    }
+
+    /// Predicts the opponent's next shape from what followed their last `order` shapes before,
+    /// and beats it. Falls back to their most common shape until it has seen that context.
+    pub struct Markov {
+        order: usize,
+        next: FastHashMap<Vec<Shape>, Vec<u64>>,
+        seen: usize,
+    }
+
+    impl Markov {
+        pub fn new(order: usize) -> Self {
+            Self {
+                order,
+                next: FastHashMap::default(),
+                seen: 0,
+            }
+        }
+    }
+
+    impl Strategy for Markov {
+        fn name(&self) -> String {
+            format!("markov (last {})", self.order)
+        }
+
+        fn choose(&mut self, game: &Game, history: &[Played], _: Shape) -> Shape {
+            // Learn from the rounds since the last call
+            for end in self.seen.max(self.order)..history.len() {
+                let context = history[end - self.order..end]
+                    .iter()
+                    .map(|p| p.you)
+                    .collect();
+                self.next
+                    .entry(context)
+                    .or_insert_with(|| vec![0; game.len()])[history[end].you.0] += 1;
+            }
+            self.seen = history.len();
+
+            let context: Vec<_> = history
+                .iter()
+                .skip(history.len().saturating_sub(self.order))
+                .map(|p| p.you)
+                .collect();
+            match self.next.get(&context) {
+                Some(counts) if context.len() == self.order => counter(game, most_common(counts)),
+                _ => Frequency.choose(game, history, Shape(0)),
+            }
+        }
+    }
+
+    /// Keeps a winning shape, and otherwise switches to the one that would have beaten the
+    /// opponent's last shape.
+    pub struct WinStayLoseShift;
+
+    impl Strategy for WinStayLoseShift {
+        fn name(&self) -> String {
+            "win-stay/lose-shift".into()
+        }
+
+        fn choose(&mut self, game: &Game, history: &[Played], _: Shape) -> Shape {
+            match history.last() {
+                None => Shape(0),
+                Some(last) if last.outcome == Outcome::Win => last.me,
+                Some(last) => counter(game, last.you),
+            }
+        }
+    }
+
+    fn most_common(counts: &[u64]) -> Shape {
+        let (shape, _) = counts
+            .iter()
+            .enumerate()
+            .max_by_key(|&(shape, &count)| (count, std::cmp::Reverse(shape)))
+            .unwrap();
+        Shape(shape)
+    }
+
+    fn counter(game: &Game, shape: Shape) -> Shape {
+        game.find_shape(shape, Outcome::Win)
+    }
+
+    /// How one strategy did over the whole guide.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Standing {
+        pub name: String,
+        pub total: u64,
+        pub rounds: Vec<Played>,
+    }
+
+    impl Standing {
+        pub fn count(&self, outcome: Outcome) -> usize {
+            self.rounds.iter().filter(|p| p.outcome == outcome).count()
+        }
+
+        /// Rounds, counting from 1, where this strategy played something other than the guide.
+        pub fn divergences(&self, guide: &[Round]) -> Vec<usize> {
+            (1..)
+                .zip(self.rounds.iter().zip(guide))
+                .filter(|(_, (played, round))| played.me != round.guide)
+                .map(|(n, _)| n)
+                .collect()
+        }
+    }
+
+    pub fn parse(game: &Game, guide: &str) -> Result<Vec<Round>> {
+        guide
+            .lines()
+            .map(|round| {
+                let (you, me) = split_round(round)?;
+                Ok(Round {
+                    you: game.opponent(you)?,
+                    guide: game.player(me)?,
+                })
+            })
+            .collect()
+    }
+
+    pub fn play(game: &Game, guide: &[Round], strategy: &mut dyn Strategy) -> Standing {
+        let mut rounds: Vec<Played> = Vec::with_capacity(guide.len());
+        let mut total = 0;
+
+        for round in guide {
+            let me = strategy.choose(game, &rounds, round.guide);
+            total += game.score(me, round.you) as u64;
+            rounds.push(Played {
+                you: round.you,
+                me,
+                outcome: game.outcome(me, round.you),
+            });
+        }
+
+        Standing {
+            name: strategy.name(),
+            total,
+            rounds,
+        }
+    }
+
+    /// Plays the guide against every strategy, with a Markov predictor of the given order.
+    pub fn tournament(game: &Game, guide: &[Round], order: usize) -> Vec<Standing> {
+        let mut strategies: Vec<Box<dyn Strategy>> = vec![
+            Box::new(Fixed),
+            Box::new(Frequency),
+            Box::new(Markov::new(order)),
+            Box::new(WinStayLoseShift),
+        ];
+        strategies
+            .iter_mut()
+            .map(|strategy| play(game, guide, strategy.as_mut()))
+            .collect()
+    }
+
+    // Rounds listed in the text report before cutting it short
+    const SHOWN_ROUNDS: usize = 20;
+
+    /// Each strategy's score, then the rounds where the Markov predictor went against the guide.
+    pub fn render(standings: &[Standing], guide: &[Round], format: Format) -> Result<String> {
+        let predictor = standings.iter().find(|s| s.name.starts_with("markov"));
+        let divergences = predictor.map(|p| p.divergences(guide)).unwrap_or_default();
+
+        match format {
+            Format::Text => {
+                let width = standings.iter().map(|s| s.name.len()).max().unwrap_or(0);
+                let mut lines = vec![format!(
+                    "{:<width$} {:>8} {:>6} {:>6} {:>6}",
+                    "strategy", "total", "wins", "draws", "losses"
+                )];
+                for s in standings {
+                    lines.push(format!(
+                        "{:<width$} {:>8} {:>6} {:>6} {:>6}",
+                        s.name,
+                        s.total,
+                        s.count(Outcome::Win),
+                        s.count(Outcome::Draw),
+                        s.count(Outcome::Lose)
+                    ));
+                }
+
+                if let Some(predictor) = predictor {
+                    let mut shown: Vec<_> = divergences
+                        .iter()
+                        .take(SHOWN_ROUNDS)
+                        .map(usize::to_string)
+                        .collect();
+                    if divergences.len() > SHOWN_ROUNDS {
+                        shown.push("...".into());
+                    }
+                    lines.push(String::new());
+                    lines.push(format!(
+                        "{} differs from the guide in {} of {} rounds: {}",
+                        predictor.name,
+                        divergences.len(),
+                        guide.len(),
+                        shown.join(", ")
+                    ));
+                }
+                Ok(lines.join("\n"))
+            }
+            Format::Json => {
+                let standings: Vec<_> = standings
+                    .iter()
+                    .map(|s| {
+                        serde_json::json!({
+                            "strategy": s.name,
+                            "total": s.total,
+                            "wins": s.count(Outcome::Win),
+                            "draws": s.count(Outcome::Draw),
+                            "losses": s.count(Outcome::Lose),
+                        })
+                    })
+                    .collect();
+                Ok(serde_json::to_string(&serde_json::json!({
+                    "standings": standings,
+                    "divergences": divergences,
+                }))?)
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_guide_matches_part_one() {
+            let game = Game::default();
+            let input = include_str!("../../data/day02-ex.txt");
+            let guide = parse(&game, input).unwrap();
+
+            let standing = play(&game, &guide, &mut Fixed);
+            assert_eq!(15, standing.total);
+            assert!(standing.divergences(&guide).is_empty());
+        }
+
+        #[test]
+        fn test_markov_learns_cycle() {
+            // The opponent cycles rock, paper, scissors: after a couple of laps, a first-order
+            // predictor always knows what's coming
+            let game = Game::default();
+            let input = "A X\nB X\nC X\n".repeat(10);
+            let guide = parse(&game, &input).unwrap();
+
+            let standing = play(&game, &guide, &mut Markov::new(1));
+            assert!(standing.rounds[6..]
+                .iter()
+                .all(|p| p.outcome == Outcome::Win));
+            assert!(standing.total > play(&game, &guide, &mut Frequency).total);
+        }
+
+        #[test]
+        fn test_win_stay_lose_shift() {
+            let game = Game::default();
+            let guide = parse(&game, "C X\nC X\nA X\nC X\n").unwrap();
+
+            let standing = play(&game, &guide, &mut WinStayLoseShift);
+            let mine: Vec<_> = standing.rounds.iter().map(|p| p.me).collect();
+            // Rock beats scissors and stays, draws with rock and shifts to paper, which then loses
+            assert_eq!(vec![Shape(0), Shape(0), Shape(0), Shape(1)], mine);
+            assert_eq!(vec![4], standing.divergences(&guide));
+        }
+
+        #[test]
+        fn test_tournament() {
+            let game = Game::default();
+            // The example's cycle, long enough for the predictors to pick it up
+            let example: Vec<_> = include_str!("../../data/day02-ex.txt").lines().collect();
+            let input = example.repeat(40).join("\n");
+            let guide = parse(&game, &input).unwrap();
+
+            let standings = tournament(&game, &guide, 2);
+            assert_eq!(4, standings.len());
+            assert_eq!(
+                super::super::part_one(&input).unwrap() as u64,
+                standings[0].total
+            );
+            assert!(render(&standings, &guide, Format::Json).is_ok());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::{self, assert_snapshot};
+
+    fn rpsls() -> Game {
+        include_str!("../../data/games/rpsls.toml").parse().unwrap()
+    }
+
+    #[test]
+    fn test_classic() {
+        let input = include_str!("../../data/day02-ex.txt");
+        assert_eq!(15, part_one(input).unwrap());
+        assert_eq!(12, part_two(input).unwrap());
+    }
+
+    #[test]
+    fn test_add_score() {
+        assert_eq!(u32::MAX, add_score(u32::MAX - 9, 9).unwrap());
+        assert!(add_score(u32::MAX - 8, 9).is_err());
+    }
+
+    #[test]
+    fn test_cyclic_dominance() {
+        let game = rpsls();
+        let shape = |name| game.shapes().find(|&s| game.name(s) == name).unwrap();
+
+        for (winner, loser) in [
+            ("Scissors", "Paper"),
+            ("Paper", "Rock"),
+            ("Rock", "Lizard"),
+            ("Lizard", "Spock"),
+            ("Spock", "Scissors"),
+            ("Scissors", "Lizard"),
+            ("Lizard", "Paper"),
+            ("Paper", "Spock"),
+            ("Spock", "Rock"),
+            ("Rock", "Scissors"),
+        ] {
+            let (winner, loser) = (shape(winner), shape(loser));
+            assert_eq!(Outcome::Win, game.outcome(winner, loser));
+            assert_eq!(Outcome::Lose, game.outcome(loser, winner));
+        }
+        for s in game.shapes() {
+            assert_eq!(Outcome::Draw, game.outcome(s, s));
+            assert_eq!(2, game.shapes().filter(|&o| game.beats(s, o)).count());
+        }
+    }
+
+    #[test]
+    fn test_rpsls_scoring() {
+        let game = rpsls();
+
+        // Spock (5) vaporizes rock, and lizard (4) poisons Spock
+        assert_eq!(11, game.score_strategy_one("A Z").unwrap());
+        assert_eq!(10, game.score_strategy_one("E Y").unwrap());
+        // Against rock, paper (2) and Spock (5) both win: take the higher scoring one
+        assert_eq!(11, game.score_strategy_two("A W").unwrap());
+        // Against scissors, paper (2) and lizard (4) both lose
+        assert_eq!(4, game.score_strategy_two("C L").unwrap());
+        assert!(game.score_strategy_one("A Q").is_err());
+    }
+
+    #[test]
+    fn test_bytes_match_checked() {
+        // Every pair of moves, in a long pseudo-random order
+        let mut seed = 2022_u64;
+        let random: Vec<_> = (0..2500)
+            .map(|_| {
+                seed = seed
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                let round = (seed >> 33) % 9;
+                format!(
+                    "{} {}",
+                    "ABC".as_bytes()[round as usize / 3] as char,
+                    "XYZ".as_bytes()[round as usize % 3] as char
+                )
+            })
+            .collect();
+        let random = random.join("\n") + "\n";
+
+        for input in [
+            include_str!("../../data/day02-ex.txt"),
+            &random,
+            "A Y\r\nB X\r\nC Z",
+        ] {
+            assert_eq!(part_one(input).unwrap(), part_one_bytes(input).unwrap());
+            assert_eq!(part_two(input).unwrap(), part_two_bytes(input).unwrap());
+        }
+
+        for input in ["A Y\nB\n", "A Q\n", "D X\n", "A  X\n", "A Y\n\nB X\n"] {
+            let (checked, bytes) = (part_one(input), part_one_bytes(input));
+            assert_eq!(
+                format!("{:#}", checked.unwrap_err()),
+                format!("{:#}", bytes.unwrap_err())
+            );
+            let (checked, bytes) = (part_two(input), part_two_bytes(input));
+            assert_eq!(
+                format!("{:#}", checked.unwrap_err()),
+                format!("{:#}", bytes.unwrap_err())
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_games() {
+        let game = include_str!("../../data/games/rpsls.toml");
+        let blocks: Vec<_> = game.split("[[shape]]").collect();
+        assert!(blocks[..5].join("[[shape]]").parse::<Game>().is_err());
+        assert!(game.replace("\"E\"", "\"A\"").parse::<Game>().is_err());
+        assert!(game.replace("\"W\"", "\"V W\"").parse::<Game>().is_err());
+        assert!(game
+            .replace("score = 0", "points = 0")
+            .parse::<Game>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_error_snapshot() {
+        let outcomes = [
+            ("A Y\nB\n", part_one("A Y\nB\n")),
+            ("A Q\n", part_one("A Q\n")),
+            ("D X\n", part_two("D X\n")),
+            ("A  X\n", part_two("A  X\n")),
+        ];
+
+        assert_snapshot("day02-errors", &snapshot::outcomes(outcomes));
+    }
+}
@@ -0,0 +1,960 @@
+use crate::collections::FastHashSet;
+use crate::output::Format;
+use anyhow::{anyhow, Context, Error, Result};
+use std::fmt;
+use std::str::FromStr;
+
+pub fn part_one(input: &str) -> Result<u32> {
+    input
+        .lines()
+        .map(|bag| {
+            let (c1, c2) = compartments(bag)?;
+            let common = c1.parse::<ItemSet>()?.intersection(c2.parse()?);
+            only_item(common).context(format!(
+                "'{}' and '{}' don't share exactly one item",
+                c1, c2
+            ))
+        })
+        .map(|item| item.and_then(value_of))
+        .sum()
+}
+
+pub fn part_two(input: &str) -> Result<u32> {
+    groups(input, 3)?.iter().map(Group::badge).sum()
+}
+
+/// [`part_one`], finding common items with hash sets rather than bitmasks.
+pub fn part_one_hashset(input: &str) -> Result<u32> {
+    input
+        .lines()
+        .map(|bag| {
+            let (c1, c2) = compartments(bag)?;
+            common_element(&[items(c1)?, items(c2)?])
+                .context(format!(
+                    "'{}' and '{}' don't share exactly one item",
+                    c1, c2
+                ))
+                .and_then(value_of)
+        })
+        .sum()
+}
+
+/// [`part_two`], finding common items with hash sets rather than bitmasks.
+pub fn part_two_hashset(input: &str) -> Result<u32> {
+    let data: Vec<_> = input.lines().collect();
+    if !data.len().is_multiple_of(3) {
+        return Err(anyhow!(
+            "{} rucksacks don't split into groups of 3",
+            data.len()
+        ));
+    }
+    let sets = data
+        .iter()
+        .map(|bag| items(bag).context(format!("invalid rucksack '{}'", bag)))
+        .collect::<Result<Vec<_>>>()?;
+
+    data.chunks(3)
+        .zip(sets.chunks(3))
+        .map(|(bags, sets)| {
+            common_element(sets)
+                .context(format!("{:?} don't share exactly one item", bags))
+                .and_then(value_of)
+        })
+        .sum()
+}
+
+/// The priority of an item, which is also its bit in an [`ItemSet`].
+fn value_of(c: char) -> Result<u32> {
+    match c {
+        'a'..='z' => Ok((c as u32) - ('a' as u32) + 1),
+        'A'..='Z' => Ok((c as u32) - ('A' as u32) + 27),
+        _ => Err(anyhow!("'{}' not a valid char", c)),
+    }
+}
+
+fn item_of(priority: u32) -> Option<char> {
+    match priority {
+        1..=26 => char::from_u32('a' as u32 + priority - 1),
+        27..=52 => char::from_u32('A' as u32 + priority - 27),
+        _ => None,
+    }
+}
+
+fn compartments(bag: &str) -> Result<(&str, &str)> {
+    let mid = bag.len() / 2;
+    if !bag.len().is_multiple_of(2) || !bag.is_char_boundary(mid) {
+        return Err(anyhow!("'{}' can't be split into two compartments", bag));
+    }
+    Ok(bag.split_at(mid))
+}
+
+// The one item in `set`, or an error saying how many there are instead
+fn only_item(set: ItemSet) -> Result<char> {
+    match set.len() {
+        1 => Ok(set.iter().next().unwrap()),
+        0 => Err(anyhow!("they share none")),
+        n => Err(anyhow!("they share {} ({})", n, set)),
+    }
+}
+
+// The items in `bag`, rejecting any that isn't a letter like `ItemSet` does
+fn items(bag: &str) -> Result<FastHashSet<char>> {
+    bag.chars().map(|c| value_of(c).map(|_| c)).collect()
+}
+
+/// The one item every bag has, with the same errors as [`only_item`].
+fn common_element(bags: &[FastHashSet<char>]) -> Result<char> {
+    let all_chars: FastHashSet<_> = ('a'..='z').chain('A'..='Z').collect();
+    let mut common: Vec<_> = bags
+        .iter()
+        .fold(all_chars, |acc, bag| {
+            acc.intersection(bag).copied().collect()
+        })
+        .into_iter()
+        .collect();
+    common.sort_by_key(|&c| value_of(c).unwrap_or(0));
+
+    match common.as_slice() {
+        [item] => Ok(*item),
+        [] => Err(anyhow!("they share none")),
+        items => Err(anyhow!(
+            "they share {} ({})",
+            items.len(),
+            items.iter().collect::<String>()
+        )),
+    }
+}
+
+/// A set of item types, as a mask with bit `n` set for the item of priority `n`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    /// Every item type, a to z then A to Z.
+    pub const ALL: Self = Self(((1 << 53) - 1) & !1);
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an item, returning whether it was new.
+    pub fn insert(&mut self, item: char) -> Result<bool> {
+        let bit = 1 << value_of(item)?;
+        let new = self.0 & bit == 0;
+        self.0 |= bit;
+        Ok(new)
+    }
+
+    pub fn contains(&self, item: char) -> bool {
+        value_of(item).is_ok_and(|priority| self.0 & (1 << priority) != 0)
+    }
+
+    pub fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// The items, lowest priority first.
+    pub fn iter(&self) -> impl Iterator<Item = char> {
+        let mut mask = self.0;
+        std::iter::from_fn(move || {
+            let priority = mask.trailing_zeros();
+            mask &= mask.checked_sub(1)?;
+            item_of(priority)
+        })
+    }
+}
+
+impl FromStr for ItemSet {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut set = Self::new();
+        for item in s.chars() {
+            set.insert(item)?;
+        }
+        Ok(set)
+    }
+}
+
+impl fmt::Display for ItemSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.iter().collect::<String>())
+    }
+}
+
+/// Consecutive rucksacks that share a badge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group<'a> {
+    /// Line of the first rucksack, counting from 1.
+    pub line: usize,
+    pub bags: Vec<&'a str>,
+    /// Items every rucksack in the group has. There should be exactly one, the badge.
+    pub common: ItemSet,
+}
+
+impl Group<'_> {
+    /// Priority of the badge, failing unless there's exactly one candidate.
+    pub fn badge(&self) -> Result<u32> {
+        only_item(self.common)
+            .context(format!("{:?} don't share exactly one item", self.bags))
+            .and_then(value_of)
+    }
+}
+
+/// Splits the rucksacks into consecutive groups of `size`, and finds what each group has in common.
+pub fn groups(input: &str, size: usize) -> Result<Vec<Group<'_>>> {
+    if size == 0 {
+        return Err(anyhow!("groups need at least one rucksack"));
+    }
+    let bags: Vec<_> = input.lines().collect();
+    if !bags.len().is_multiple_of(size) {
+        return Err(anyhow!(
+            "{} rucksacks don't split into groups of {}",
+            bags.len(),
+            size
+        ));
+    }
+
+    bags.chunks(size)
+        .enumerate()
+        .map(|(idx, bags)| {
+            let common = bags.iter().try_fold(ItemSet::ALL, |common, bag| {
+                let items: ItemSet = bag.parse().context(format!("invalid rucksack '{}'", bag))?;
+                Ok::<_, Error>(common.intersection(items))
+            })?;
+            Ok(Group {
+                line: idx * size + 1,
+                bags: bags.to_vec(),
+                common,
+            })
+        })
+        .collect()
+}
+
+/// Lists the groups that don't have exactly one common item, then the sum of the badges of the
+/// rest.
+pub fn render_groups(groups: &[Group], format: Format) -> Result<String> {
+    let bad: Vec<_> = groups.iter().filter(|g| g.common.len() != 1).collect();
+    let total: u32 = groups.iter().filter_map(|g| g.badge().ok()).sum();
+
+    match format {
+        Format::Text => {
+            let mut lines: Vec<_> = bad
+                .iter()
+                .map(|g| {
+                    let lines = g.line..g.line + g.bags.len();
+                    match g.common.len() {
+                        0 => format!("lines {}-{}: no common item", lines.start, lines.end - 1),
+                        n => format!(
+                            "lines {}-{}: {} common items ({})",
+                            lines.start,
+                            lines.end - 1,
+                            n,
+                            g.common
+                        ),
+                    }
+                })
+                .collect();
+            lines.push(format!(
+                "{} groups, {} with exactly one badge, badges sum to {}",
+                groups.len(),
+                groups.len() - bad.len(),
+                total
+            ));
+            Ok(lines.join("\n"))
+        }
+        Format::Json => {
+            let bad: Vec<_> = bad
+                .iter()
+                .map(|g| {
+                    serde_json::json!({
+                        "line": g.line,
+                        "common": g.common.to_string(),
+                    })
+                })
+                .collect();
+            Ok(serde_json::to_string(&serde_json::json!({
+                "groups": groups.len(),
+                "total": total,
+                "problems": bad,
+            }))?)
+        }
+    }
+}
+
+/// Finding the badge groups when the rucksacks have been shuffled.
+///
+/// Any three rucksacks sharing exactly one item type could be a group, so this searches for a way
+/// to split every rucksack into such triples. The search always extends the group of the first
+/// rucksack left, and abandons a branch as soon as some rucksack left can't be grouped at all
+/// because none of its items is in two other rucksacks still left.
+pub mod partition {
+    use super::{Format, ItemSet};
+    use anyhow::{anyhow, Context, Result};
+
+    /// Nodes the search visits before giving up, on finding a grouping or on telling whether it is
+    /// unique.
+    pub const NODE_LIMIT: u64 = 10_000_000;
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Partition {
+        /// Each group as indices into the input lines, counting from 0, with its badge.
+        pub groups: Vec<([usize; 3], char)>,
+        /// Whether this is the only grouping, or `None` if the search gave up before finding out.
+        pub unique: Option<bool>,
+    }
+
+    pub fn partition(input: &str) -> Result<Partition> {
+        partition_within(input, NODE_LIMIT)
+    }
+
+    fn partition_within(input: &str, limit: u64) -> Result<Partition> {
+        let bags = input
+            .lines()
+            .map(|bag| bag.parse().context(format!("invalid rucksack '{}'", bag)))
+            .collect::<Result<Vec<ItemSet>>>()?;
+        if !bags.len().is_multiple_of(3) {
+            return Err(anyhow!(
+                "{} rucksacks don't split into groups of 3",
+                bags.len()
+            ));
+        }
+
+        let mut search = Search::new(&bags, limit);
+        search.run();
+        let gave_up = search.nodes > limit;
+        let mut solutions = search.solutions.into_iter();
+        let groups = match solutions.next() {
+            Some(groups) => groups,
+            None if gave_up => {
+                return Err(anyhow!(
+                    "gave up after {} nodes without finding a grouping",
+                    limit
+                ))
+            }
+            None => {
+                return Err(anyhow!(
+                    "the rucksacks can't be split into groups sharing exactly one item"
+                ))
+            }
+        };
+        let unique = if gave_up {
+            None
+        } else {
+            Some(solutions.next().is_none())
+        };
+
+        let groups = groups
+            .into_iter()
+            .map(|group| {
+                let common = group
+                    .iter()
+                    .fold(ItemSet::ALL, |common, &bag| common.intersection(bags[bag]));
+                (group, common.iter().next().unwrap())
+            })
+            .collect();
+        Ok(Partition { groups, unique })
+    }
+
+    struct Search<'a> {
+        bags: &'a [ItemSet],
+        used: Vec<bool>,
+        /// How many unused rucksacks hold each item, by priority.
+        holders: [usize; 53],
+        current: Vec<[usize; 3]>,
+        solutions: Vec<Vec<[usize; 3]>>,
+        nodes: u64,
+        limit: u64,
+    }
+
+    impl<'a> Search<'a> {
+        fn new(bags: &'a [ItemSet], limit: u64) -> Self {
+            let mut holders = [0; 53];
+            for bag in bags {
+                for item in bag.iter() {
+                    holders[super::value_of(item).unwrap() as usize] += 1;
+                }
+            }
+            Self {
+                bags,
+                used: vec![false; bags.len()],
+                holders,
+                current: vec![],
+                solutions: vec![],
+                nodes: 0,
+                limit,
+            }
+        }
+
+        // Stops at two solutions, which is enough to know the first isn't unique
+        fn run(&mut self) {
+            self.nodes += 1;
+            if self.solutions.len() >= 2 || self.nodes > self.limit {
+                return;
+            }
+            let Some(first) = self.used.iter().position(|&used| !used) else {
+                self.solutions.push(self.current.clone());
+                return;
+            };
+            if !self.viable() {
+                return;
+            }
+
+            let n = self.bags.len();
+            self.take(first, true);
+            for second in first + 1..n {
+                let badges = self.bags[first].intersection(self.bags[second]);
+                if self.used[second] || badges.is_empty() {
+                    continue;
+                }
+                self.take(second, true);
+                for third in second + 1..n {
+                    if self.used[third] || badges.intersection(self.bags[third]).len() != 1 {
+                        continue;
+                    }
+                    self.take(third, true);
+                    self.current.push([first, second, third]);
+                    self.run();
+                    self.current.pop();
+                    self.take(third, false);
+                }
+                self.take(second, false);
+            }
+            self.take(first, false);
+        }
+
+        fn take(&mut self, bag: usize, used: bool) {
+            self.used[bag] = used;
+            for item in self.bags[bag].iter() {
+                let holders = &mut self.holders[super::value_of(item).unwrap() as usize];
+                if used {
+                    *holders -= 1;
+                } else {
+                    *holders += 1;
+                }
+            }
+        }
+
+        // Every unused rucksack needs an item that two other unused rucksacks also hold
+        fn viable(&self) -> bool {
+            let mut shared = ItemSet::new();
+            for (priority, &holders) in self.holders.iter().enumerate() {
+                if holders >= 3 {
+                    shared
+                        .insert(super::item_of(priority as u32).unwrap())
+                        .unwrap();
+                }
+            }
+            self.bags
+                .iter()
+                .zip(self.used.iter())
+                .all(|(bag, &used)| used || !bag.intersection(shared).is_empty())
+        }
+    }
+
+    impl Partition {
+        pub fn render(&self, format: Format) -> Result<String> {
+            let unique = match self.unique {
+                Some(true) => "the only grouping",
+                Some(false) => "not the only grouping",
+                None => "gave up before finding out whether it is the only grouping",
+            };
+            match format {
+                Format::Text => {
+                    let mut lines: Vec<_> = self
+                        .groups
+                        .iter()
+                        .map(|([a, b, c], badge)| {
+                            format!("{}  lines {}, {}, {}", badge, a + 1, b + 1, c + 1)
+                        })
+                        .collect();
+                    lines.push(format!("{} groups, {}", self.groups.len(), unique));
+                    Ok(lines.join("\n"))
+                }
+                Format::Json => {
+                    let groups: Vec<_> = self
+                        .groups
+                        .iter()
+                        .map(|(bags, badge)| {
+                            let lines: Vec<_> = bags.iter().map(|bag| bag + 1).collect();
+                            serde_json::json!({"badge": badge.to_string(), "lines": lines})
+                        })
+                        .collect();
+                    Ok(serde_json::to_string(&serde_json::json!({
+                        "groups": groups,
+                        "unique": self.unique,
+                    }))?)
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn check(input: &str, partition: &Partition) {
+            let bags: Vec<ItemSet> = input.lines().map(|bag| bag.parse().unwrap()).collect();
+            let mut seen: Vec<_> = partition.groups.iter().flat_map(|(g, _)| *g).collect();
+            seen.sort();
+            assert_eq!((0..bags.len()).collect::<Vec<_>>(), seen);
+
+            for ([a, b, c], badge) in partition.groups.iter() {
+                let common = bags[*a].intersection(bags[*b]).intersection(bags[*c]);
+                assert_eq!(badge.to_string(), common.to_string());
+            }
+        }
+
+        #[test]
+        fn test_shuffled_example() {
+            let lines: Vec<_> = include_str!("../../data/day03-ex.txt").lines().collect();
+            let shuffled = [4, 0, 2, 5, 1, 3].map(|i| lines[i]).join("\n");
+
+            // Other triples besides the puzzle's share exactly one item too
+            let partition = partition(&shuffled).unwrap();
+            check(&shuffled, &partition);
+            assert_eq!(Some(false), partition.unique);
+        }
+
+        #[test]
+        fn test_unique() {
+            let input = "xz\nab\nxy\nac\nxw\nad\n";
+            let partition = partition(input).unwrap();
+            check(input, &partition);
+            assert_eq!(Some(true), partition.unique);
+            assert_eq!(vec![([0, 2, 4], 'x'), ([1, 3, 5], 'a')], partition.groups);
+        }
+
+        #[test]
+        fn test_no_partition() {
+            assert!(partition("ab\ncd\nef\n").is_err());
+            // Each item is in three rucksacks, but whichever triple takes one leaves a dud
+            assert!(partition("ab\nac\nad\nbc\nbd\ncd\n").is_err());
+            assert!(partition("ab\nab\n").is_err());
+        }
+
+        #[test]
+        fn test_shuffled_groups() {
+            let mut seed = 3_u64;
+            let mut next = |range: usize| {
+                seed = seed
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (seed >> 33) as usize % range
+            };
+
+            // 16 groups, badged a to p, whose members draw their other items from disjoint pools
+            let items: Vec<char> = ItemSet::ALL.iter().collect();
+            let mut bags = vec![];
+            for badge in &items[..16] {
+                for pool in items[16..].chunks(12) {
+                    let mut bag = badge.to_string();
+                    bag.extend(pool.iter().filter(|_| next(2) == 0));
+                    bags.push(bag);
+                }
+            }
+            for i in (1..bags.len()).rev() {
+                bags.swap(i, next(i + 1));
+            }
+
+            let input = bags.join("\n");
+            let partition = partition(&input).unwrap();
+            check(&input, &partition);
+        }
+
+        #[test]
+        fn test_node_limit() {
+            let input = include_str!("../../data/day03-ex.txt");
+            let err = partition_within(input, 2).unwrap_err();
+            assert_eq!(
+                "gave up after 2 nodes without finding a grouping",
+                err.to_string()
+            );
+            assert!(partition_within(input, 100).is_ok());
+        }
+    }
+}
+
+/// Planning how to repack each rucksack so its compartments share no item type.
+///
+/// A swap exchanges one item from each compartment, so both keep their size. In the end every item
+/// type is wholly in one compartment, and the types in the first must add up to exactly half the
+/// items: choosing them is a subset sum over at most 52 types, solved by dynamic programming for the
+/// fewest items moved, then the lowest total priority moved.
+pub mod repack {
+    use super::{compartments, value_of, Format};
+    use anyhow::Result;
+    use std::collections::BTreeMap;
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Plan {
+        pub swaps: usize,
+        /// Total priority of every item that changes compartment, both ways.
+        pub moved_priority: u32,
+        /// The compartments after repacking. Items that stay keep their places, and each one
+        /// moved out is replaced by the next one moved in.
+        pub packed: (String, String),
+    }
+
+    // How many of an item type each compartment holds
+    #[derive(Debug, Clone, Copy)]
+    struct Counts {
+        priority: u32,
+        first: usize,
+        second: usize,
+    }
+
+    /// The cheapest repacking of `bag`, or `None` if no choice of item types fills exactly one
+    /// compartment.
+    pub fn plan(bag: &str) -> Result<Option<Plan>> {
+        let (c1, c2) = compartments(bag)?;
+        let mut by_type: BTreeMap<char, Counts> = BTreeMap::new();
+        for (compartment, items) in [c1, c2].iter().enumerate() {
+            for item in items.chars() {
+                let counts = by_type.entry(item).or_insert(Counts {
+                    priority: value_of(item)?,
+                    first: 0,
+                    second: 0,
+                });
+                match compartment {
+                    0 => counts.first += 1,
+                    _ => counts.second += 1,
+                }
+            }
+        }
+        let types: Vec<_> = by_type.into_iter().collect();
+        let size = c1.chars().count();
+
+        // best[i][n]: fewest items moved, then least priority, when the first i types put n items
+        // in the first compartment
+        let mut best = vec![vec![None; size + 1]; types.len() + 1];
+        best[0][0] = Some((0, 0));
+        for (i, (_, counts)) in types.iter().enumerate() {
+            for n in 0..=size {
+                let Some((moved, priority)) = best[i][n] else {
+                    continue;
+                };
+                // All of this type goes to the second compartment, or all to the first
+                let second = (
+                    moved + counts.first,
+                    priority + counts.priority * counts.first as u32,
+                );
+                let first = (
+                    moved + counts.second,
+                    priority + counts.priority * counts.second as u32,
+                );
+                relax(&mut best[i + 1][n], second);
+                if n + counts.first + counts.second <= size {
+                    relax(&mut best[i + 1][n + counts.first + counts.second], first);
+                }
+            }
+        }
+        let Some((moved, moved_priority)) = best[types.len()][size] else {
+            return Ok(None);
+        };
+
+        // Walk back through the table to find which types stay in the first compartment
+        let mut first = vec![];
+        let mut n = size;
+        for (i, (item, counts)) in types.iter().enumerate().rev() {
+            let (moved, priority) = best[i + 1][n].unwrap();
+            let from_second = best[i][n]
+                .map(|(m, p)| (m + counts.first, p + counts.priority * counts.first as u32));
+            if from_second != Some((moved, priority)) {
+                first.push(*item);
+                n -= counts.first + counts.second;
+            }
+        }
+
+        let mut into_first = c2.chars().filter(|item| first.contains(item));
+        let mut into_second = c1.chars().filter(|item| !first.contains(item));
+        let packed = (
+            c1.chars()
+                .map(|item| match first.contains(&item) {
+                    true => item,
+                    false => into_first.next().unwrap(),
+                })
+                .collect(),
+            c2.chars()
+                .map(|item| match first.contains(&item) {
+                    true => into_second.next().unwrap(),
+                    false => item,
+                })
+                .collect(),
+        );
+
+        Ok(Some(Plan {
+            swaps: moved / 2,
+            moved_priority,
+            packed,
+        }))
+    }
+
+    fn relax(slot: &mut Option<(usize, u32)>, candidate: (usize, u32)) {
+        if slot.is_none_or(|best| candidate < best) {
+            *slot = Some(candidate);
+        }
+    }
+
+    pub fn plan_all(input: &str) -> Result<Vec<Option<Plan>>> {
+        input.lines().map(plan).collect()
+    }
+
+    /// The rucksacks that need repacking or can't be repacked, then totals across all of them.
+    pub fn render(plans: &[Option<Plan>], format: Format) -> Result<String> {
+        let planned: Vec<_> = plans.iter().flatten().collect();
+        let already = planned.iter().filter(|plan| plan.swaps == 0).count();
+        let impossible = plans.len() - planned.len();
+        let swaps: usize = planned.iter().map(|plan| plan.swaps).sum();
+        let moved: u32 = planned.iter().map(|plan| plan.moved_priority).sum();
+
+        match format {
+            Format::Text => {
+                let mut lines = vec![];
+                for (line, plan) in (1..).zip(plans) {
+                    match plan {
+                        None => lines.push(format!("line {:>4}: can't be repacked", line)),
+                        Some(plan) if plan.swaps > 0 => lines.push(format!(
+                            "line {:>4}: {} swap(s), priority {:>4} moved  {} | {}",
+                            line, plan.swaps, plan.moved_priority, plan.packed.0, plan.packed.1
+                        )),
+                        Some(_) => {}
+                    }
+                }
+                lines.push(format!(
+                    "{} rucksacks: {} already fine, {} repacked with {} swap(s) moving priority {}, \
+                     {} impossible",
+                    plans.len(),
+                    already,
+                    planned.len() - already,
+                    swaps,
+                    moved,
+                    impossible
+                ));
+                Ok(lines.join("\n"))
+            }
+            Format::Json => {
+                let rucksacks: Vec<_> = (1..)
+                    .zip(plans)
+                    .map(|(line, plan): (usize, _)| match plan {
+                        None => serde_json::json!({"line": line, "possible": false}),
+                        Some(plan) => serde_json::json!({
+                            "line": line,
+                            "possible": true,
+                            "swaps": plan.swaps,
+                            "moved_priority": plan.moved_priority,
+                            "packed": [plan.packed.0, plan.packed.1],
+                        }),
+                    })
+                    .collect();
+                Ok(serde_json::to_string(&serde_json::json!({
+                    "rucksacks": rucksacks,
+                    "already_fine": already,
+                    "impossible": impossible,
+                    "swaps": swaps,
+                    "moved_priority": moved,
+                }))?)
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::days::day03::ItemSet;
+
+        #[test]
+        fn test_plan() {
+            // Moving the a over costs priority 1 + 2; moving b and c instead would cost 3 + 1
+            let repacked = plan("abca").unwrap().unwrap();
+            assert_eq!((1, 3), (repacked.swaps, repacked.moved_priority));
+            assert_eq!(("aa".to_string(), "cb".to_string()), repacked.packed);
+
+            let repacked = plan("abcd").unwrap().unwrap();
+            assert_eq!((0, 0), (repacked.swaps, repacked.moved_priority));
+        }
+
+        #[test]
+        fn test_impossible() {
+            // Three a and one b can't fill a compartment of two
+            assert_eq!(None, plan("aaab").unwrap());
+            assert!(plan("abc").is_err());
+            assert!(plan("a1b2").is_err());
+        }
+
+        #[test]
+        fn test_plans_are_disjoint() {
+            let mut seed = 50_u64;
+            let mut next = |range: usize| {
+                seed = seed
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (seed >> 33) as usize % range
+            };
+
+            // Few item types, so that most rucksacks mix them across compartments
+            let items: Vec<char> = "abcdefgHIJ".chars().collect();
+            let bags: Vec<String> = (0..300)
+                .map(|_| {
+                    (0..2 * (1 + next(16)))
+                        .map(|_| items[next(items.len())])
+                        .collect()
+                })
+                .collect();
+
+            let mut planned = 0;
+            for bag in &bags {
+                let Some(repacked) = plan(bag).unwrap() else {
+                    continue;
+                };
+                planned += 1;
+                let (first, second): (ItemSet, ItemSet) = (
+                    repacked.packed.0.parse().unwrap(),
+                    repacked.packed.1.parse().unwrap(),
+                );
+                assert!(first.intersection(second).is_empty(), "{}", bag);
+
+                let mut before: Vec<_> = bag.chars().collect();
+                let mut after: Vec<_> = repacked
+                    .packed
+                    .0
+                    .chars()
+                    .chain(repacked.packed.1.chars())
+                    .collect();
+                before.sort();
+                after.sort();
+                assert_eq!(before, after);
+
+                let (first, _) = &repacked.packed;
+                let changed = first.chars().zip(bag.chars()).filter(|(a, b)| a != b);
+                assert!(changed.count() <= repacked.swaps);
+            }
+            assert!(planned > bags.len() / 2, "only {} planned", planned);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::snapshot::{self, assert_snapshot};
+
+    #[test]
+    fn test_value_of() {
+        assert_eq!(value_of('p').unwrap(), 16_u32);
+        assert_eq!(value_of('L').unwrap(), 38_u32);
+    }
+
+    #[test]
+    fn test_common_element() {
+        let [a, b, c, d, e] = ["pmCn", "Czyx", "abCd", "pmxy", "xy"].map(|bag| items(bag).unwrap());
+        assert_eq!('C', common_element(&[a.clone(), b, c]).unwrap());
+        assert!(common_element(&[a.clone(), d]).is_err());
+        assert!(common_element(&[a, e]).is_err());
+        assert!(items("ab1").is_err());
+    }
+
+    #[test]
+    fn test_hashset_matches_bitmask() {
+        let example = include_str!("../../data/day03-ex.txt");
+        assert_eq!(
+            part_one(example).unwrap(),
+            part_one_hashset(example).unwrap()
+        );
+        assert_eq!(
+            part_two(example).unwrap(),
+            part_two_hashset(example).unwrap()
+        );
+
+        for input in [
+            "abab\n",
+            "abcd\n",
+            "aAbB\n",
+            "a1a1\n",
+            "aa\nb a \n",
+            "a\u{e9}a\n",
+        ] {
+            assert_eq!(
+                format!("{:#}", part_one(input).unwrap_err()),
+                format!("{:#}", part_one_hashset(input).unwrap_err())
+            );
+        }
+        for input in [
+            "ab\ncd\nef\n",
+            "abc\nabd\nabe\n",
+            "a1\na2\na3\n",
+            "ab\nac\nad\nb!\nb\nb\n",
+            "ab\nab\n",
+        ] {
+            assert_eq!(
+                format!("{:#}", part_two(input).unwrap_err()),
+                format!("{:#}", part_two_hashset(input).unwrap_err())
+            );
+        }
+    }
+
+    #[test]
+    fn test_item_set() {
+        let mut set: ItemSet = "pmCn".parse().unwrap();
+        assert_eq!(4, set.len());
+        assert!(set.contains('C') && !set.contains('c') && !set.contains('1'));
+        assert!(!set.insert('m').unwrap());
+        assert!(set.insert('Z').unwrap());
+        assert!(set.insert('!').is_err());
+
+        let other: ItemSet = "Czyxa".parse().unwrap();
+        assert_eq!("C", set.intersection(other).to_string());
+        assert_eq!("amnpxyzCZ", set.union(other).to_string());
+        assert_eq!(52, ItemSet::ALL.len());
+        assert_eq!(Some('Z'), ItemSet::ALL.iter().last());
+        assert!(ItemSet::new().iter().next().is_none());
+    }
+
+    #[test]
+    fn test_groups() {
+        let input = include_str!("../../data/day03-ex.txt");
+        let badges: Vec<_> = groups(input, 3)
+            .unwrap()
+            .iter()
+            .map(|g| g.common.to_string())
+            .collect();
+        assert_eq!(vec!["r", "Z"], badges);
+
+        let groups = groups(input, 2).unwrap();
+        assert_eq!(3, groups.len());
+        assert_eq!(
+            (3, "qvwBT".into()),
+            (groups[1].line, groups[1].common.to_string())
+        );
+        assert!(groups[1].badge().is_err());
+        assert!(super::groups(input, 4).is_err());
+    }
+
+    #[test]
+    fn test_error_snapshot() {
+        let outcomes = [
+            (
+                "vJrwpWtwJgWrhcsFMMfFFhFp\nabc\n",
+                part_one("vJrwpWtwJgWrhcsFMMfFFhFp\nabc\n"),
+            ),
+            ("a\u{e9}a\n", part_one("a\u{e9}a\n")),
+            ("abcd\n", part_one("abcd\n")),
+            ("abab\n", part_one("abab\n")),
+            ("ab\ncd\nef\n", part_two("ab\ncd\nef\n")),
+        ];
+
+        assert_snapshot("day03-errors", &snapshot::outcomes(outcomes));
+    }
+}
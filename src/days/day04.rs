@@ -0,0 +1,108 @@
+use anyhow::{Context, Result};
+use std::str::FromStr;
+
+pub fn part_one(input: &str) -> Result<usize> {
+    let count = assignments(input)?
+        .iter()
+        .filter(|(a, b)| a.contains(b) || b.contains(a))
+        .count();
+    Ok(count)
+}
+
+pub fn part_two(input: &str) -> Result<usize> {
+    let count = assignments(input)?
+        .iter()
+        .filter(|(a, b)| Assignment::overlaps(a, b))
+        .count();
+    Ok(count)
+}
+
+fn assignments(input: &str) -> Result<Vec<(Assignment, Assignment)>> {
+    input
+        .lines()
+        .map(|line| {
+            let (a, b) = line
+                .split_once(',')
+                .context(format!("'{}' is not a valid line", line))?;
+            let a: Assignment = a.parse()?;
+            let b: Assignment = b.parse()?;
+
+            Ok((a, b))
+        })
+        .collect()
+}
+
+struct Assignment {
+    start: u32,
+    end: u32,
+}
+
+impl Assignment {
+    pub const fn new(start: u32, end: u32) -> Self {
+        Self { start, end }
+    }
+
+    pub fn contains(&self, other: &Self) -> bool {
+        self.start <= other.start && self.end >= other.end
+    }
+
+    pub fn overlaps(a: &Self, b: &Self) -> bool {
+        a.contains(b)
+            || b.contains(a)
+            || (a.start <= b.start && b.start <= a.end)
+            || (b.start <= a.start && a.start <= b.end)
+    }
+}
+
+impl FromStr for Assignment {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s
+            .split_once('-')
+            .context(format!("'{}' is not a valid assignment", s))?;
+        let start: u32 = start
+            .parse()
+            .with_context(|| format!("invalid start in '{}'", s))?;
+        let end: u32 = end
+            .parse()
+            .with_context(|| format!("invalid end in '{}'", s))?;
+
+        Ok(Assignment::new(start, end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::{self, assert_snapshot};
+
+    #[rustfmt::skip]
+    #[allow(clippy::bool_assert_comparison)]
+    #[test]
+    fn test_overlap() {
+        assert_eq!(true, Assignment::overlaps(&Assignment::new(5, 7), &Assignment::new(7, 9)));
+        assert_eq!(true, Assignment::overlaps(&Assignment::new(2, 8), &Assignment::new(3, 7)));
+        assert_eq!(true, Assignment::overlaps(&Assignment::new(6, 6), &Assignment::new(4, 6)));
+        assert_eq!(true, Assignment::overlaps(&Assignment::new(2, 6), &Assignment::new(4, 8)));
+
+        assert_eq!(true, Assignment::overlaps(&Assignment::new(7, 9), &Assignment::new(5, 7)));
+        assert_eq!(true, Assignment::overlaps(&Assignment::new(3, 7), &Assignment::new(2, 8)));
+        assert_eq!(true, Assignment::overlaps(&Assignment::new(4, 6), &Assignment::new(6, 6)));
+        assert_eq!(true, Assignment::overlaps(&Assignment::new(4, 8), &Assignment::new(2, 6)));
+
+        assert_eq!(false, Assignment::overlaps(&Assignment::new(2, 6), &Assignment::new(7, 8)));
+        assert_eq!(false, Assignment::overlaps(&Assignment::new(7, 8), &Assignment::new(2, 6)));
+    }
+
+    #[test]
+    fn test_error_snapshot() {
+        let outcomes = [
+            ("2-4,6-8\n2-4\n", part_one("2-4,6-8\n2-4\n")),
+            ("2-x,6-8\n", part_one("2-x,6-8\n")),
+            ("2-4,6-99999999999\n", part_two("2-4,6-99999999999\n")),
+        ];
+
+        assert_snapshot("day04-errors", &snapshot::outcomes(outcomes));
+    }
+}
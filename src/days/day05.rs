@@ -0,0 +1,205 @@
+use anyhow::{Context, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::fmt;
+use std::str::FromStr;
+
+pub fn part_one(input: &str) -> Result<String> {
+    let (mut stacks, ops) = parse(input)?;
+    stacks.rearrange_9000(&ops)?;
+    stacks.tops()
+}
+
+pub fn part_two(input: &str) -> Result<String> {
+    let (mut stacks, ops) = parse(input)?;
+    stacks.rearrange_9001(&ops)?;
+    stacks.tops()
+}
+
+fn parse(input: &str) -> Result<(Stacks, Vec<Op>)> {
+    let (setup, ops) = input
+        .split_once("\n\n")
+        .context("Input not in expected format")?;
+
+    let stacks: Stacks = setup.parse()?;
+    let ops: Vec<Op> = ops
+        .lines()
+        .map(|line| line.parse())
+        .collect::<Result<_>>()?;
+
+    Ok((stacks, ops))
+}
+
+#[derive(Debug, Clone, Default)]
+struct Stacks(Vec<Vec<char>>);
+
+impl Stacks {
+    pub fn add(&mut self, stack: &[char]) {
+        self.0.push(stack.into());
+    }
+
+    fn rearrange(&mut self, ops: &[Op], strategy: impl Fn(&mut Vec<char>)) -> Result<()> {
+        for op in ops {
+            let from = op
+                .from
+                .checked_sub(1)
+                .and_then(|idx| self.0.get_mut(idx))
+                .context(format!("Invalid from index '{}'", op.from))?;
+            let mut krates: Vec<_> = (0..op.count)
+                .map(|_| from.pop().context(format!("Stack '{}' exhausted", op.from)))
+                .collect::<Result<_>>()?;
+
+            strategy(&mut krates);
+
+            let to = op
+                .to
+                .checked_sub(1)
+                .and_then(|idx| self.0.get_mut(idx))
+                .context(format!("Invalid to index '{}'", op.to))?;
+            krates.iter().for_each(|&krate| to.push(krate));
+        }
+
+        Ok(())
+    }
+
+    pub fn rearrange_9000(&mut self, ops: &[Op]) -> Result<()> {
+        self.rearrange(ops, |_| {})
+    }
+
+    pub fn rearrange_9001(&mut self, ops: &[Op]) -> Result<()> {
+        self.rearrange(ops, |krates| krates.reverse())
+    }
+
+    pub fn tops(self) -> Result<String> {
+        let mut res = String::new();
+
+        for stack in self.0.iter() {
+            let top = stack.last().context("empty stack!")?;
+            res.push(*top);
+        }
+
+        Ok(res)
+    }
+}
+
+/// Draws the stacks the way the puzzle does, with each stack's number underneath.
+impl fmt::Display for Stacks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let height = self.0.iter().map(Vec::len).max().unwrap_or(0);
+
+        for level in (0..height).rev() {
+            let row: Vec<_> = self
+                .0
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(krate) => format!("[{}]", krate),
+                    None => "   ".into(),
+                })
+                .collect();
+            writeln!(f, "{}", row.join(" ").trim_end())?;
+        }
+
+        let names: Vec<_> = (1..=self.0.len()).map(|n| format!(" {} ", n)).collect();
+        write!(f, "{}", names.join(" ").trim_end())
+    }
+}
+
+impl FromStr for Stacks {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut stacks = Stacks::default();
+
+        let lines: Vec<_> = s.lines().rev().collect();
+        let (&names, crates) = lines.split_first().context("invalid input")?;
+
+        for (idx, char) in names.chars().enumerate() {
+            if char.is_ascii_alphanumeric() {
+                let stack = crates
+                    .iter()
+                    .filter_map(|&c| c.chars().nth(idx))
+                    .filter(|c| c.is_ascii_alphanumeric())
+                    .collect::<Vec<_>>();
+                stacks.add(&stack);
+            }
+        }
+        Ok(stacks)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Op {
+    count: usize,
+    from: usize,
+    to: usize,
+}
+
+impl Op {
+    pub const fn new(count: usize, from: usize, to: usize) -> Self {
+        Self { count, from, to }
+    }
+}
+
+impl FromStr for Op {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref RE: Regex = Regex::new(r"move (\d+) from (\d+) to (\d+)").unwrap();
+        }
+
+        let caps = RE
+            .captures(s)
+            .context(format!("'{}' does not match regex", s))?;
+
+        let count: usize = caps[1].parse()?;
+        let from: usize = caps[2].parse()?;
+        let to: usize = caps[3].parse()?;
+
+        Ok(Op::new(count, from, to))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::{self, assert_snapshot};
+
+    #[test]
+    fn test_parse_op() {
+        let line = "move 12 from 4 to 7";
+        let op: Op = line.parse().unwrap();
+
+        assert_eq!(op, Op::new(12, 4, 7));
+    }
+
+    #[test]
+    fn test_render_snapshot() {
+        let (mut stacks, ops) = parse(include_str!("../../data/day05-ex.txt")).unwrap();
+        let before = stacks.to_string();
+        stacks.rearrange_9000(&ops).unwrap();
+
+        assert_snapshot("day05-stacks", &format!("{}\n\n{}\n", before, stacks));
+    }
+
+    #[test]
+    fn test_error_snapshot() {
+        let setup = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\n";
+        let inputs = [
+            format!("{}move 1 from 0 to 1\n", setup),
+            format!("{}move 1 from 2 to 4\n", setup),
+            format!("{}move 4 from 1 to 2\n", setup),
+            format!("{}move one from 1 to 2\n", setup),
+            "move 1 from 2 to 1\n".into(),
+        ];
+        let outcomes = [
+            (&inputs[0], part_one(&inputs[0])),
+            (&inputs[1], part_one(&inputs[1])),
+            (&inputs[2], part_two(&inputs[2])),
+            (&inputs[3], part_two(&inputs[3])),
+            (&inputs[4], part_one(&inputs[4])),
+        ];
+
+        assert_snapshot("day05-errors", &snapshot::outcomes(outcomes));
+    }
+}
@@ -0,0 +1,115 @@
+use crate::collections::{FastHashMap, FastHashSet};
+use anyhow::{Context, Result};
+
+pub fn part_one(input: &str) -> Result<usize> {
+    find_unique(input, 4).context("could not find start-of-packet marker")
+}
+
+pub fn part_two(input: &str) -> Result<usize> {
+    find_unique(input, 14).context("could not find start-of-message marker")
+}
+
+/// [`part_one`], sliding a window along rather than checking each position from scratch.
+pub fn part_one_window(input: &str) -> Result<usize> {
+    find_unique_window(input, 4).context("could not find start-of-packet marker")
+}
+
+/// [`part_two`], sliding a window along rather than checking each position from scratch.
+pub fn part_two_window(input: &str) -> Result<usize> {
+    find_unique_window(input, 14).context("could not find start-of-message marker")
+}
+
+fn find_unique(msg: &str, len: usize) -> Option<usize> {
+    // Work on chars rather than byte slices so non-ASCII input can't split a char in half
+    let chars: Vec<_> = msg.chars().collect();
+    for idx in len..=chars.len() {
+        let substr = &chars[idx - len..idx];
+        if substr.iter().collect::<FastHashSet<_>>().len() == len {
+            return Some(idx);
+        }
+    }
+    None
+}
+
+// Keeps a count of each char in the window, and of how many chars appear more than once, updating
+// both as one char enters and another leaves
+fn find_unique_window(msg: &str, len: usize) -> Option<usize> {
+    let chars: Vec<_> = msg.chars().collect();
+    let mut counts: FastHashMap<char, usize> = FastHashMap::default();
+    let mut repeated = 0;
+
+    for (idx, &c) in chars.iter().enumerate() {
+        let count = counts.entry(c).or_default();
+        *count += 1;
+        if *count == 2 {
+            repeated += 1;
+        }
+
+        if idx >= len {
+            let count = counts.entry(chars[idx - len]).or_default();
+            *count -= 1;
+            if *count == 1 {
+                repeated -= 1;
+            }
+        }
+
+        if idx + 1 >= len && repeated == 0 {
+            return Some(idx + 1);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::{self, assert_snapshot};
+
+    #[test]
+    fn test_find_unique() {
+        assert_eq!(Some(7), find_unique("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 4));
+        assert_eq!(Some(19), find_unique("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 14));
+        assert_eq!(Some(4), find_unique("abcd", 4));
+    }
+
+    #[test]
+    fn test_find_unique_window() {
+        let cases = [
+            "mjqjpqmgbljsphdztnvjfqwrcgsmlb",
+            "bvwbjplbgvbhsrlpgdmjqwftvncz",
+            "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg",
+            "abcd",
+            "aaaa",
+            "",
+        ];
+        for msg in cases {
+            for len in [4, 14] {
+                assert_eq!(
+                    find_unique(msg, len),
+                    find_unique_window(msg, len),
+                    "{}",
+                    msg
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_error_snapshot() {
+        let outcomes = [
+            ("aaaaaaaa\n", part_one("aaaaaaaa\n")),
+            (
+                "\u{e9}\u{e9}\u{e9}\u{e9}\u{e9}",
+                part_one("\u{e9}\u{e9}\u{e9}\u{e9}\u{e9}"),
+            ),
+            ("abcd\n", part_two("abcd\n")),
+        ];
+
+        assert_snapshot("day06-errors", &snapshot::outcomes(outcomes));
+    }
+
+    #[test]
+    fn test_multibyte_marker() {
+        assert_eq!(4, part_one("\u{e9}bcde").unwrap());
+    }
+}
@@ -0,0 +1,301 @@
+use self::fs::FileSystem;
+use anyhow::Result;
+
+// https://smallcultfollowing.com/babysteps/blog/2015/04/06/modeling-graphs-in-rust-using-vector-indices/
+
+/// Not solved yet, so this just dumps the parsed filesystem.
+pub fn part_one(input: &str) -> Result<String> {
+    let filesystem: FileSystem = input.parse()?;
+    Ok(filesystem.to_string().trim_end().to_string())
+}
+
+pub mod fs {
+    pub use anyhow::Result;
+    use anyhow::{anyhow, Context, Error};
+    use std::collections::hash_map::Entry;
+    use std::collections::HashMap;
+    use std::fmt;
+    use std::str::FromStr;
+
+    #[derive(Debug, Clone, Copy)]
+    pub struct NodeIdx(usize);
+
+    #[derive(Debug)]
+    pub struct FileSystem(Vec<Node>);
+
+    impl FileSystem {
+        pub fn new() -> Self {
+            let root = FolderData {
+                parent: None,
+                children: HashMap::new(),
+            };
+            Self(vec![Node::Folder(root)])
+        }
+
+        pub fn root_mut(&mut self) -> CurrentDirMut<'_> {
+            CurrentDirMut {
+                fs: self,
+                idx: NodeIdx(0),
+            }
+        }
+
+        /// Looks up an absolute path such as `/a/e`. The root is `/`.
+        pub fn find(&self, path: &str) -> Option<NodeIdx> {
+            path.split('/')
+                .filter(|name| !name.is_empty())
+                .try_fold(NodeIdx(0), |idx, name| match &self.0[idx.0] {
+                    Node::Folder(folder_data) => folder_data.children.get(name).copied(),
+                    Node::File(_) => None,
+                })
+        }
+
+        /// Size of a file, or the total size of everything under a folder.
+        pub fn size(&self, idx: NodeIdx) -> usize {
+            match &self.0[idx.0] {
+                Node::File(size) => *size,
+                Node::Folder(folder_data) => folder_data
+                    .children
+                    .values()
+                    .map(|&child| self.size(child))
+                    .sum(),
+            }
+        }
+
+        /// The absolute path and total size of every folder, sorted by path.
+        pub fn folder_sizes(&self) -> Vec<(String, usize)> {
+            let mut sizes = vec![];
+            let mut stack = vec![(String::from("/"), NodeIdx(0))];
+
+            while let Some((path, idx)) = stack.pop() {
+                if let Node::Folder(folder_data) = &self.0[idx.0] {
+                    for (name, &child) in folder_data.children.iter() {
+                        stack.push((format!("{}{}/", path, name), child));
+                    }
+                    sizes.push((path, self.size(idx)));
+                }
+            }
+            sizes.sort();
+            sizes
+        }
+    }
+
+    /// Draws the tree the way the puzzle does, with each folder's children sorted by name.
+    impl fmt::Display for FileSystem {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.fmt_node(f, "/", NodeIdx(0), 0)
+        }
+    }
+
+    impl FileSystem {
+        fn fmt_node(
+            &self,
+            f: &mut fmt::Formatter<'_>,
+            name: &str,
+            idx: NodeIdx,
+            depth: usize,
+        ) -> fmt::Result {
+            let indent = "  ".repeat(depth);
+            match &self.0[idx.0] {
+                Node::File(size) => writeln!(f, "{}- {} (file, size={})", indent, name, size),
+                Node::Folder(folder_data) => {
+                    writeln!(f, "{}- {} (dir)", indent, name)?;
+                    let mut children: Vec<_> = folder_data.children.iter().collect();
+                    children.sort_by_key(|(name, _)| *name);
+                    for (name, &child) in children {
+                        self.fmt_node(f, name, child, depth + 1)?;
+                    }
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    impl Default for FileSystem {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl FromStr for FileSystem {
+        type Err = Error;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let mut filesystem = Self::new();
+            let mut cur_dir = filesystem.root_mut();
+
+            let mut lines = s.lines().peekable();
+            while let Some(cmd) = lines.next() {
+                let tokens: Vec<_> = cmd.split_ascii_whitespace().collect();
+                match &tokens[..] {
+                    ["$", "ls"] => {
+                        while let Some(fs_entry) = lines.next_if(|line| !line.starts_with('$')) {
+                            let tokens: Vec<_> = fs_entry.split_ascii_whitespace().collect();
+                            match &tokens[..] {
+                                ["dir", name] => {
+                                    cur_dir
+                                        .add_folder(*name)
+                                        .context(format!("Could not add folder {}", name))?;
+                                }
+                                [size, name] => {
+                                    let size: usize = size.parse().context(format!(
+                                        "invalid file size {} for file {}",
+                                        size, name
+                                    ))?;
+                                    cur_dir
+                                        .add_file(*name, size)
+                                        .context(format!("Could not add file {}", name))?;
+                                }
+                                _ => {
+                                    return Err(anyhow!("invalid filesystem entry '{}'", fs_entry))
+                                }
+                            }
+                        }
+                    }
+                    ["$", "cd", dir] => cur_dir = cur_dir.change(dir)?,
+                    _ => return Err(anyhow!("unknown command '{}'", cmd)),
+                }
+            }
+            Ok(filesystem)
+        }
+    }
+
+    #[derive(Debug)]
+    pub enum Node {
+        File(usize),
+        Folder(FolderData),
+    }
+
+    #[derive(Debug)]
+    pub struct FolderData {
+        parent: Option<NodeIdx>,
+        children: HashMap<String, NodeIdx>,
+    }
+
+    #[allow(dead_code)]
+    pub struct CurrentDir<'fs> {
+        fs: &'fs FileSystem,
+        node: NodeIdx,
+    }
+
+    #[derive(Debug)]
+    pub struct CurrentDirMut<'fs> {
+        fs: &'fs mut FileSystem,
+        idx: NodeIdx,
+    }
+
+    impl<'fs> CurrentDirMut<'fs> {
+        fn add_child(&mut self, name: impl Into<String>, child: Node) -> Result<()> {
+            let child_idx = NodeIdx(self.fs.0.len());
+            let node = &mut self.fs.0[self.idx.0];
+            if let Node::Folder(folder_data) = node {
+                match folder_data.children.entry(name.into()) {
+                    Entry::Vacant(v) => {
+                        v.insert(child_idx);
+                        self.fs.0.push(child);
+                        Ok(())
+                    }
+                    Entry::Occupied(o) => Err(anyhow!(
+                        "Attempted to insert duplicate child entry (name: {}, idx: {})",
+                        o.key(),
+                        self.idx.0
+                    )),
+                }
+            } else {
+                Err(anyhow!("Cannot add children to file (idx: {})", self.idx.0))
+            }
+        }
+
+        pub fn add_file(&mut self, name: impl Into<String>, size: usize) -> Result<()> {
+            self.add_child(name, Node::File(size))
+        }
+
+        pub fn add_folder(&mut self, name: impl Into<String>) -> Result<()> {
+            let child = Node::Folder(FolderData {
+                parent: Some(self.idx),
+                children: HashMap::new(),
+            });
+            self.add_child(name, child)
+        }
+
+        pub fn change(self, path: impl AsRef<str>) -> Result<Self> {
+            let node = &mut self.fs.0[self.idx.0];
+            if let Node::Folder(folder_data) = node {
+                match path.as_ref() {
+                    "/" => Ok(CurrentDirMut {
+                        fs: self.fs,
+                        idx: NodeIdx(0),
+                    }),
+                    ".." => {
+                        let parent_idx = folder_data.parent.unwrap_or(NodeIdx(0));
+                        Ok(CurrentDirMut {
+                            fs: self.fs,
+                            idx: parent_idx,
+                        })
+                    }
+                    child => {
+                        let child_idx = folder_data
+                            .children
+                            .get(child)
+                            .context(format!("No child {} (idx: {})", child, self.idx.0))
+                            .copied()?;
+                        Ok(CurrentDirMut {
+                            fs: self.fs,
+                            idx: child_idx,
+                        })
+                    }
+                }
+            } else {
+                Err(anyhow!(
+                    "Cannot cd into a file: {} (idx: {})",
+                    path.as_ref(),
+                    self.idx.0
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::{self, assert_snapshot};
+
+    #[test]
+    fn test_queries() {
+        let input = include_str!("../../data/day07-ex.txt");
+        let filesystem: FileSystem = input.parse().unwrap();
+
+        let e = filesystem.find("/a/e").unwrap();
+        assert_eq!(584, filesystem.size(e));
+        assert_eq!(
+            14848514,
+            filesystem.size(filesystem.find("/b.txt").unwrap())
+        );
+        assert!(filesystem.find("/a/missing").is_none());
+        assert!(filesystem.find("/b.txt/c").is_none());
+
+        let sizes = filesystem.folder_sizes();
+        assert_eq!(("/".to_string(), 48381165), sizes[0]);
+        assert_eq!(("/a/e/".to_string(), 584), sizes[2]);
+    }
+
+    #[test]
+    fn test_render_snapshot() {
+        let filesystem: FileSystem = include_str!("../../data/day07-ex.txt").parse().unwrap();
+        assert_snapshot("day07-tree", &filesystem.to_string());
+    }
+
+    #[test]
+    fn test_error_snapshot() {
+        let outcomes = [
+            "$ cd /\n$ ls\nabc b.txt\n",
+            "$ cd /\n$ ls\ndir a\ndir a\n",
+            "$ cd /\n$ ls\n10 b.txt\n$ cd b.txt\n$ ls\n20 c.txt\n",
+            "$ cd missing\n",
+            "$ rm -rf /\n",
+        ]
+        .map(|input| (input, part_one(input)));
+
+        assert_snapshot("day07-errors", &snapshot::outcomes(outcomes));
+    }
+}
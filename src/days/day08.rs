@@ -0,0 +1,279 @@
+use crate::collections::DenseSet;
+use anyhow::{anyhow, Context, Error, Result};
+use std::collections::HashSet;
+use std::str::FromStr;
+
+pub fn part_one(input: &str) -> Result<usize> {
+    let forest: Forest = input.parse()?;
+    Ok(forest.visible().len())
+}
+
+pub fn part_two(input: &str) -> Result<usize> {
+    let forest: Forest = input.parse()?;
+
+    forest
+        .iter_trees()
+        .map(|tree| forest.scenic_score(&tree))
+        .max()
+        .context("could not calculate most scenic tree")
+}
+
+/// [`part_two`], working out every tree's viewing distances in one pass per row and column.
+pub fn part_two_stack(input: &str) -> Result<usize> {
+    let forest: Forest = input.parse()?;
+
+    forest
+        .scenic_scores()
+        .into_iter()
+        .max()
+        .context("could not calculate most scenic tree")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Tree {
+    pub x: usize,
+    pub y: usize,
+    pub height: u8,
+}
+
+impl Tree {
+    pub fn new(x: usize, y: usize, height: u8) -> Self {
+        Self { x, y, height }
+    }
+
+    pub fn visible(trees: &mut impl Iterator<Item = Self>) -> Vec<Tree> {
+        let mut answer = vec![];
+
+        if let Some(first) = trees.next() {
+            let mut max = first.height;
+            answer.push(first);
+
+            for tree in trees {
+                if tree.height > max {
+                    max = tree.height;
+                    answer.push(tree);
+                }
+            }
+        }
+
+        answer
+    }
+
+    pub fn count_visible(&self, trees: &mut impl Iterator<Item = Self>) -> usize {
+        // Advance the iterator until we hit ourself
+        trees.take_while(|other| self.ne(other)).for_each(|_| {});
+
+        // Start counting!
+        let mut count = 0;
+        for tree in trees {
+            count += 1;
+            if self.height <= tree.height {
+                break;
+            }
+        }
+        count
+    }
+}
+
+#[derive(Debug)]
+pub struct Forest {
+    width: usize,
+    height: usize,
+    data: Vec<u8>,
+}
+
+// Alias to make signatures a bit easier
+pub trait TreeIter<'a>:
+    DoubleEndedIterator<Item = Tree> + ExactSizeIterator<Item = Tree> + 'a
+{
+}
+impl<'a, T> TreeIter<'a> for T where
+    T: DoubleEndedIterator<Item = Tree> + ExactSizeIterator<Item = Tree> + 'a
+{
+}
+
+impl Forest {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn tree(&self, x: usize, y: usize) -> Option<Tree> {
+        if x < self.width && y < self.height {
+            Some(Tree::new(x, y, self.data[y * self.width + x]))
+        } else {
+            None
+        }
+    }
+
+    /// Coordinates of every tree that can be seen from outside the forest.
+    pub fn visible(&self) -> DenseSet {
+        let mut visible = DenseSet::new();
+        for row in 0..self.height() {
+            visible.extend(coords(Tree::visible(&mut self.iter_row(row))));
+            visible.extend(coords(Tree::visible(&mut self.iter_row(row).rev())));
+        }
+
+        for col in 0..self.width() {
+            visible.extend(coords(Tree::visible(&mut self.iter_col(col))));
+            visible.extend(coords(Tree::visible(&mut self.iter_col(col).rev())));
+        }
+        visible
+    }
+
+    pub fn iter_trees(&self) -> impl TreeIter<'_> {
+        self.data.iter().enumerate().map(|(idx, &height)| {
+            let x = idx % self.width;
+            let y = idx / self.width;
+            Tree::new(x, y, height)
+        })
+    }
+
+    pub fn iter_row(&self, row: usize) -> impl TreeIter<'_> {
+        self.iter_trees().skip(row * self.width).take(self.width())
+    }
+
+    pub fn iter_col(&self, col: usize) -> impl TreeIter<'_> {
+        self.iter_trees().skip(col).step_by(self.width)
+    }
+
+    pub fn scenic_score(&self, tree: &Tree) -> usize {
+        let right = tree.count_visible(&mut self.iter_row(tree.y));
+        let left = tree.count_visible(&mut self.iter_row(tree.y).rev());
+        let down = tree.count_visible(&mut self.iter_col(tree.x));
+        let up = tree.count_visible(&mut self.iter_col(tree.x).rev());
+
+        right * left * down * up
+    }
+}
+
+fn coords(trees: Vec<Tree>) -> impl Iterator<Item = (i32, i32)> {
+    trees.into_iter().map(|tree| (tree.x as i32, tree.y as i32))
+}
+
+// How far each tree in a line can see back towards the start of the line. A stack holds the trees
+// that could still block the view of a later one, so every tree is pushed and popped at most once.
+fn viewing_distances(line: impl Iterator<Item = Tree>) -> Vec<usize> {
+    let mut blockers: Vec<(usize, u8)> = vec![];
+
+    line.enumerate()
+        .map(|(idx, tree)| {
+            while blockers.last().is_some_and(|&(_, h)| h < tree.height) {
+                blockers.pop();
+            }
+            let distance = blockers.last().map_or(idx, |&(blocker, _)| idx - blocker);
+            blockers.push((idx, tree.height));
+            distance
+        })
+        .collect()
+}
+
+impl Forest {
+    /// The scenic score of every tree, in the same order as [`Forest::iter_trees`].
+    pub fn scenic_scores(&self) -> Vec<usize> {
+        let mut scores = vec![1; self.data.len()];
+
+        for row in 0..self.height {
+            let left = viewing_distances(self.iter_row(row));
+            let mut right = viewing_distances(self.iter_row(row).rev());
+            right.reverse();
+            for (x, (l, r)) in left.into_iter().zip(right).enumerate() {
+                scores[row * self.width + x] *= l * r;
+            }
+        }
+
+        for col in 0..self.width {
+            let up = viewing_distances(self.iter_col(col));
+            let mut down = viewing_distances(self.iter_col(col).rev());
+            down.reverse();
+            for (y, (u, d)) in up.into_iter().zip(down).enumerate() {
+                scores[y * self.width + col] *= u * d;
+            }
+        }
+
+        scores
+    }
+}
+
+impl FromStr for Forest {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut data = vec![];
+
+        let mut row_lens = HashSet::new();
+        let mut height = 0;
+
+        for row in s.lines() {
+            height += 1;
+            row_lens.insert(row.len());
+
+            for c in row.chars() {
+                let tree = c
+                    .to_digit(10)
+                    .context(format!("'{}' is not a valid tree height", c))?;
+                data.push(tree as u8);
+            }
+        }
+
+        match row_lens.into_iter().collect::<Vec<_>>()[..] {
+            [width] if width > 0 => Ok(Self {
+                width,
+                height,
+                data,
+            }),
+            [] | [_] => Err(anyhow!("no trees")),
+            _ => Err(anyhow!("rows have different lengths")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::{self, assert_snapshot};
+
+    #[test]
+    fn test_scenic_score() {
+        let forest: Forest = "30373\n25512\n65332\n33549\n35390".parse().unwrap();
+
+        assert_eq!(4, forest.scenic_score(&Tree::new(2, 1, 5)));
+        assert_eq!(8, forest.scenic_score(&Tree::new(2, 3, 5)));
+        assert_eq!(0, forest.scenic_score(&Tree::new(0, 0, 3)));
+    }
+
+    #[test]
+    fn test_scenic_scores() {
+        let forest: Forest = "30373\n25512\n65332\n33549\n35390".parse().unwrap();
+        let naive: Vec<_> = forest
+            .iter_trees()
+            .map(|t| forest.scenic_score(&t))
+            .collect();
+
+        assert_eq!(naive, forest.scenic_scores());
+    }
+
+    #[test]
+    fn test_visible() {
+        let forest: Forest = "30373\n25512\n65332\n33549\n35390".parse().unwrap();
+
+        assert_eq!(21, forest.visible().len());
+        assert_eq!(Some(Tree::new(2, 1, 5)), forest.tree(2, 1));
+        assert_eq!(None, forest.tree(5, 0));
+    }
+
+    #[test]
+    fn test_error_snapshot() {
+        let outcomes = [
+            ("30373\n25a12\n", part_one("30373\n25a12\n")),
+            ("30373\n2551\n", part_one("30373\n2551\n")),
+            ("\n\n", part_two("\n\n")),
+            ("", part_two("")),
+        ];
+
+        assert_snapshot("day08-errors", &snapshot::outcomes(outcomes));
+    }
+}
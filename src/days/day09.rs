@@ -0,0 +1,212 @@
+use crate::collections::DenseSet;
+use anyhow::{anyhow, Context, Error, Result};
+use std::ops::Deref;
+use std::str::FromStr;
+
+pub fn part_one(input: &str) -> Result<usize> {
+    count_tail_positions::<2>(&motions(input)?)
+}
+
+pub fn part_two(input: &str) -> Result<usize> {
+    count_tail_positions::<10>(&motions(input)?)
+}
+
+fn motions(input: &str) -> Result<Vec<Motion>> {
+    input.lines().map(|line| line.parse()).collect()
+}
+
+fn count_tail_positions<const KNOTS: usize>(motions: &[Motion]) -> Result<usize> {
+    let mut rope = Rope::<KNOTS>::default();
+    let mut tail_positions = DenseSet::new();
+
+    let tail = rope.tail()?;
+    tail_positions.insert(tail.x, tail.y);
+
+    for motion in motions {
+        for _ in 0..motion.steps {
+            rope.step(&motion.dir)?;
+            let tail = rope.tail()?;
+            tail_positions.insert(tail.x, tail.y);
+        }
+    }
+
+    Ok(tail_positions.len())
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Position {
+    pub x: i32,
+    pub y: i32,
+}
+
+pub struct Rope<const N: usize>([Position; N]);
+
+impl<const N: usize> Rope<N> {
+    pub fn tail(&self) -> Result<Position> {
+        self.0.last().copied().context("rope has no knots")
+    }
+
+    pub fn step(&mut self, dir: &Direction) -> Result<()> {
+        step_knots(&mut self.0, dir)
+    }
+}
+
+/// Moves the first knot one step in `dir`, with every other knot following the one before it.
+/// This is what [`Rope::step`] does, for ropes whose length is only known at runtime.
+pub fn step_knots(knots: &mut [Position], dir: &Direction) -> Result<()> {
+    if let Some(head) = knots.first_mut() {
+        match dir {
+            Direction::Left => head.x -= 1,
+            Direction::Right => head.x += 1,
+            Direction::Up => head.y += 1,
+            Direction::Down => head.y -= 1,
+        }
+    };
+
+    for idx in 1..knots.len() {
+        let (leads, follows) = knots.split_at_mut(idx);
+        update_follower(&leads[idx - 1], &mut follows[0])?;
+    }
+
+    Ok(())
+}
+
+fn update_follower(lead: &Position, follow: &mut Position) -> Result<()> {
+    let dx = lead.x - follow.x;
+    let dy = lead.y - follow.y;
+
+    match (dx, dy) {
+        // If lead and follow are touching, no update needed.
+        (-1..=1, -1..=1) => {}
+        // Handle lead +- 2 along same rank as follow
+        (-2 | 2, 0) => follow.x += dx / 2,
+        (0, -2 | 2) => follow.y += dy / 2,
+        // Handle L-shaped difference
+        (-2 | 2, -1 | 1) => {
+            follow.y = lead.y;
+            follow.x += dx / 2;
+        }
+        (-1 | 1, -2 | 2) => {
+            follow.x = lead.x;
+            follow.y += dy / 2;
+        }
+        // Handle large diagonal jump. This can only happen in N>2 ropes, if the lead itself
+        // had an L-shaped difference with it's lead.
+        (-2 | 2, -2 | 2) => {
+            follow.x += dx / 2;
+            follow.y += dy / 2;
+        }
+        // Anything else is invalid!
+        (dx, dy) => return Err(anyhow!("can't update follower for delta ({}, {})", dx, dy)),
+    }
+
+    Ok(())
+}
+
+impl<const N: usize> Default for Rope<N> {
+    fn default() -> Self {
+        Self([Position::default(); N])
+    }
+}
+
+impl<const N: usize> Deref for Rope<N> {
+    type Target = [Position; N];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl FromStr for Direction {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "L" => Ok(Direction::Left),
+            "R" => Ok(Direction::Right),
+            "U" => Ok(Direction::Up),
+            "D" => Ok(Direction::Down),
+            _ => Err(anyhow!("'{}' not a valid direction", s)),
+        }
+    }
+}
+
+pub struct Motion {
+    pub steps: u32,
+    pub dir: Direction,
+}
+
+impl FromStr for Motion {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (dir, steps) = s
+            .split_once(' ')
+            .context(format!("'{}' is not a valid movement", s))?;
+
+        let steps: u32 = steps
+            .parse()
+            .with_context(|| format!("invalid steps in '{}'", s))?;
+        let dir = dir.parse()?;
+
+        Ok(Self { steps, dir })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::{self, assert_snapshot};
+
+    #[test]
+    fn test_count_tail_positions() {
+        let input = include_str!("../../data/day09-ex.txt");
+        assert_eq!(13, part_one(input).unwrap());
+        assert_eq!(1, part_two(input).unwrap());
+    }
+
+    #[test]
+    fn test_step_knots() {
+        let mut rope = Rope::<3>::default();
+        let mut knots = vec![Position::default(); 3];
+        for dir in [
+            Direction::Right,
+            Direction::Right,
+            Direction::Up,
+            Direction::Up,
+        ] {
+            rope.step(&dir).unwrap();
+            step_knots(&mut knots, &dir).unwrap();
+        }
+
+        assert_eq!(rope.to_vec(), knots);
+        assert_eq!(Position { x: 1, y: 1 }, rope.tail().unwrap());
+    }
+
+    #[test]
+    fn test_error_snapshot() {
+        let outcomes = [
+            ("R 4\nX 2\n", part_one("R 4\nX 2\n")),
+            ("R -4\n", part_one("R -4\n")),
+            ("R4\n", part_two("R4\n")),
+            ("R 4\nU", part_two("R 4\nU")),
+        ];
+
+        assert_snapshot("day09-errors", &snapshot::outcomes(outcomes));
+    }
+
+    #[test]
+    fn test_rope_errors() {
+        assert!(Rope::<0>::default().tail().is_err());
+
+        let mut rope = Rope::<2>([Position { x: 0, y: 0 }, Position { x: 5, y: 0 }]);
+        assert!(rope.step(&Direction::Up).is_err());
+    }
+}
@@ -0,0 +1,183 @@
+//! Guessing which day an unlabelled input belongs to from its shape.
+//!
+//! Each day has a signature: the line formats its input is made of, plus a few structural checks
+//! for days whose lines look alike (calorie lists and tree grids are both lines of digits). A day
+//! scores the fraction of lines fitting its formats, scaled down when the structure is off.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::cmp::Ordering;
+
+/// Below this, the best guess isn't trusted enough to act on.
+pub const MIN_CONFIDENCE: f64 = 0.8;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Guess {
+    pub day: u32,
+    /// From 0 (nothing fits) to 1 (every line fits, and the structure is right).
+    pub confidence: f64,
+}
+
+/// Scores every day with a signature against `input`, best guess first.
+pub fn detect(input: &str) -> Vec<Guess> {
+    let lines: Vec<_> = input.lines().collect();
+
+    let mut guesses: Vec<_> = (1..=9)
+        .map(|day| Guess {
+            day,
+            confidence: score(day, &lines),
+        })
+        .collect();
+    guesses.sort_by(|a, b| {
+        b.confidence
+            .partial_cmp(&a.confidence)
+            .unwrap_or(Ordering::Equal)
+    });
+    guesses
+}
+
+fn score(day: u32, lines: &[&str]) -> f64 {
+    lazy_static! {
+        static ref NUMBER: Regex = Regex::new(r"^\d+$").unwrap();
+        static ref ROUND: Regex = Regex::new(r"^[ABC] [XYZ]$").unwrap();
+        static ref RUCKSACK: Regex = Regex::new(r"^[a-zA-Z]+$").unwrap();
+        static ref PAIR: Regex = Regex::new(r"^\d+-\d+,\d+-\d+$").unwrap();
+        static ref CRATES: Regex = Regex::new(r"^(\[[A-Z]\]|   )( (\[[A-Z]\]|   ))*\s*$").unwrap();
+        static ref LABELS: Regex = Regex::new(r"^( \d  ?)+$").unwrap();
+        static ref MOVE: Regex = Regex::new(r"^move \d+ from \d+ to \d+$").unwrap();
+        static ref SIGNAL: Regex = Regex::new(r"^[a-z]+$").unwrap();
+        static ref TERMINAL: Regex = Regex::new(r"^(\$ cd \S+|\$ ls|dir \S+|\d+ \S+)$").unwrap();
+        static ref MOTION: Regex = Regex::new(r"^[LRUD] \d+$").unwrap();
+    }
+
+    let fits = |re: &Regex| fraction(lines, |line| re.is_match(line));
+    let has_blank = lines.iter().any(|line| line.is_empty());
+    let count = |re: &Regex| lines.iter().filter(|line| re.is_match(line)).count();
+
+    match day {
+        // Groups of numbers separated by blank lines
+        1 => {
+            let fit = fraction(lines, |line| line.is_empty() || NUMBER.is_match(line));
+            if has_blank {
+                fit
+            } else {
+                fit / 2.0
+            }
+        }
+        2 => fits(&ROUND),
+        // Many rucksacks of an even number of items
+        3 => {
+            let fit = fraction(lines, |line| {
+                RUCKSACK.is_match(line) && line.len().is_multiple_of(2)
+            });
+            if lines.len() > 1 {
+                fit
+            } else {
+                fit / 4.0
+            }
+        }
+        4 => fits(&PAIR),
+        // A crate diagram and a list of moves
+        5 => {
+            if count(&MOVE) == 0 || count(&CRATES) == 0 || !has_blank {
+                return 0.0;
+            }
+            fraction(lines, |line| {
+                line.is_empty()
+                    || MOVE.is_match(line)
+                    || CRATES.is_match(line)
+                    || LABELS.is_match(line)
+            })
+        }
+        // A single line of signal
+        6 => match lines {
+            [line] if line.len() >= 4 && SIGNAL.is_match(line) => 1.0,
+            _ => 0.0,
+        },
+        // A terminal session, starting with a `cd`
+        7 => {
+            let fit = fits(&TERMINAL);
+            match lines.first() {
+                Some(line) if line.starts_with("$ cd") => fit,
+                _ => fit / 2.0,
+            }
+        }
+        // A grid of digits: every line the same width, with no gaps
+        8 => {
+            let width = lines.first().map_or(0, |line| line.len());
+            let fit = fraction(lines, |line| line.len() == width && NUMBER.is_match(line));
+            if lines.len() > 1 && !has_blank {
+                fit
+            } else {
+                fit / 4.0
+            }
+        }
+        9 => fits(&MOTION),
+        _ => 0.0,
+    }
+}
+
+fn fraction(lines: &[&str], fits: impl Fn(&str) -> bool) -> f64 {
+    if lines.is_empty() {
+        return 0.0;
+    }
+    lines.iter().filter(|line| fits(line)).count() as f64 / lines.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inputs;
+
+    #[test]
+    fn test_detect_inputs() {
+        let inputs: [(u32, &[u8]); 9] = [
+            (1, include_bytes!("../data/day01.txt")),
+            (2, include_bytes!("../data/day02.txt")),
+            (3, include_bytes!("../data/day03.txt")),
+            (4, include_bytes!("../data/day04.txt")),
+            (5, include_bytes!("../data/day05.txt")),
+            (6, include_bytes!("../data/day06.txt")),
+            (7, include_bytes!("../data/day07.txt")),
+            (8, include_bytes!("../data/day08.txt")),
+            (9, include_bytes!("../data/day09.txt")),
+        ];
+
+        for (day, bytes) in inputs {
+            if inputs::is_locked(bytes) {
+                continue;
+            }
+            let guess = detect(std::str::from_utf8(bytes).unwrap())[0];
+            assert_eq!(day, guess.day);
+            assert!(guess.confidence >= MIN_CONFIDENCE, "day {}", day);
+        }
+    }
+
+    #[test]
+    fn test_detect_examples() {
+        let examples = [
+            (1, include_str!("../data/day01-ex.txt")),
+            (2, include_str!("../data/day02-ex.txt")),
+            (3, include_str!("../data/day03-ex.txt")),
+            (4, include_str!("../data/day04-ex.txt")),
+            (5, include_str!("../data/day05-ex.txt")),
+            (6, include_str!("../data/day06-ex.txt")),
+            (7, include_str!("../data/day07-ex.txt")),
+            (8, include_str!("../data/day08-ex.txt")),
+            (9, include_str!("../data/day09-ex.txt")),
+        ];
+
+        for (day, input) in examples {
+            let guesses = detect(input);
+            assert_eq!(day, guesses[0].day);
+            assert_eq!(1.0, guesses[0].confidence, "day {}", day);
+            assert!(guesses[1].confidence < MIN_CONFIDENCE, "day {}", day);
+        }
+    }
+
+    #[test]
+    fn test_detect_nonsense() {
+        assert_eq!(0.0, detect("")[0].confidence);
+        assert!(detect("hello, world\n42\n")[0].confidence < MIN_CONFIDENCE);
+    }
+}
@@ -0,0 +1,259 @@
+//! Client for the puzzle site: downloading inputs and submitting answers.
+//!
+//! Inputs are cached on disk and never downloaded twice. Requests carry an identifying
+//! User-Agent and are spaced out by a minimum interval, which is tracked in a stamp file so it
+//! holds across separate runs as well.
+
+use crate::config::Config;
+use crate::inputs::{self, Key};
+use anyhow::{anyhow, Context, Result};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const USER_AGENT: &str = concat!(
+    "aoc22/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/gmhorn/aoc22)"
+);
+
+pub struct RateLimit {
+    pub interval: Duration,
+    /// File recording when the last request went out.
+    pub stamp: PathBuf,
+}
+
+impl RateLimit {
+    /// Blocks until `interval` has passed since the last request, then records a new one.
+    fn wait(&self) -> Result<()> {
+        let last = std::fs::read_to_string(&self.stamp)
+            .ok()
+            .and_then(|stamp| stamp.trim().parse().ok())
+            .map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+
+        if let Some(last) = last {
+            let since = SystemTime::now().duration_since(last).unwrap_or_default();
+            if since < self.interval {
+                thread::sleep(self.interval - since);
+            }
+        }
+
+        if let Some(dir) = self.stamp.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+        std::fs::write(&self.stamp, now.to_string())
+            .context(format!("could not write '{}'", self.stamp.display()))
+    }
+}
+
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    year: u32,
+    session: String,
+    limit: RateLimit,
+    /// Key to lock downloaded inputs with, if inputs are kept encrypted.
+    key_file: PathBuf,
+}
+
+impl Client {
+    pub fn new(
+        base_url: &str,
+        year: u32,
+        session: &str,
+        user_agent: &str,
+        limit: RateLimit,
+    ) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(user_agent)
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            year,
+            session: session.to_string(),
+            limit,
+            key_file: PathBuf::new(),
+        }
+    }
+
+    /// Builds a client from the config, taking the session token from `AOC_SESSION` first.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let session = std::env::var(SESSION_VAR)
+            .ok()
+            .or_else(|| config.session.clone())
+            .context(format!(
+                "no session token; set {} or `session` in the config",
+                SESSION_VAR
+            ))?;
+        let limit = RateLimit {
+            interval: Duration::from_secs(config.fetch_interval),
+            stamp: config.data_dir.join(".last-fetch"),
+        };
+
+        let mut client = Self::new(
+            &config.base_url,
+            config.year,
+            &session,
+            &config.user_agent,
+            limit,
+        );
+        client.key_file = config.key_file.clone();
+        Ok(client)
+    }
+
+    /// Returns the input for `day`, downloading it to `dest` unless it's already there.
+    pub fn fetch(&self, day: u32, dest: &Path) -> Result<String> {
+        if dest.exists() {
+            return inputs::read(dest, &self.key_file);
+        }
+
+        self.limit.wait()?;
+        let url = format!("{}/{}/day/{}/input", self.base_url, self.year, day);
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+
+        let input = match response {
+            Ok(response) => response.into_string()?,
+            Err(ureq::Error::Status(404, _)) => {
+                return Err(anyhow!("day {} input is not available yet", day))
+            }
+            Err(ureq::Error::Status(400, _)) => {
+                return Err(anyhow!("session token rejected fetching day {}", day))
+            }
+            Err(ureq::Error::Status(code, _)) => {
+                return Err(anyhow!("fetching day {} failed with status {}", day, code))
+            }
+            Err(err) => return Err(err).context(format!("could not fetch day {}", day)),
+        };
+
+        self.store(&input, dest)?;
+        Ok(input)
+    }
+
+    /// Posts an answer for one part of a day, returning the response page.
+    pub fn submit(&self, day: u32, part: usize, answer: &str) -> Result<String> {
+        self.limit.wait()?;
+        let url = format!("{}/{}/day/{}/answer", self.base_url, self.year, day);
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+
+        match response {
+            Ok(response) => Ok(response.into_string()?),
+            Err(ureq::Error::Status(code, _)) => Err(anyhow!(
+                "submitting day {} part {} failed with status {}",
+                day,
+                part,
+                code
+            )),
+            Err(err) => Err(err).context(format!("could not submit day {} part {}", day, part)),
+        }
+    }
+
+    // Writes via a temporary file so an interrupted download never leaves a partial input behind
+    fn store(&self, input: &str, dest: &Path) -> Result<()> {
+        let bytes = if self.key_file.is_file() {
+            inputs::lock(input.as_bytes(), &Key::load(&self.key_file)?)?
+        } else {
+            input.as_bytes().to_vec()
+        };
+
+        if let Some(dir) = dest.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let tmp = dest.with_extension("part");
+        std::fs::write(&tmp, bytes).context(format!("could not write '{}'", tmp.display()))?;
+        std::fs::rename(&tmp, dest).context(format!("could not write '{}'", dest.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub::StubServer;
+    use std::time::Instant;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc22-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn client(url: &str, dir: &Path, interval: Duration) -> Client {
+        let limit = RateLimit {
+            interval,
+            stamp: dir.join(".last-fetch"),
+        };
+        Client::new(url, 2022, "abc123", USER_AGENT, limit)
+    }
+
+    #[test]
+    fn test_fetch_caches() {
+        let server = StubServer::start(|request| match request.path.as_str() {
+            "/2022/day/1/input" => (200, "1000\n2000\n".into()),
+            _ => (404, "not found".into()),
+        });
+        let dir = temp_dir("fetch-cache");
+        let client = client(&server.url, &dir, Duration::ZERO);
+
+        let dest = dir.join("day01.txt");
+        assert_eq!("1000\n2000\n", client.fetch(1, &dest).unwrap());
+        assert_eq!("1000\n2000\n", client.fetch(1, &dest).unwrap());
+        assert_eq!("1000\n2000\n", std::fs::read_to_string(&dest).unwrap());
+
+        let requests = server.requests();
+        assert_eq!(1, requests.len());
+        assert_eq!("GET", requests[0].method);
+        assert_eq!(Some("session=abc123"), requests[0].header("Cookie"));
+        assert_eq!(Some(USER_AGENT), requests[0].header("User-Agent"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_errors_not_cached() {
+        let server = StubServer::start(|request| match request.path.as_str() {
+            "/2022/day/2/input" => (400, "bad session".into()),
+            _ => (404, "not found".into()),
+        });
+        let dir = temp_dir("fetch-errors");
+        let client = client(&server.url, &dir, Duration::ZERO);
+
+        let err = client.fetch(25, &dir.join("day25.txt")).unwrap_err();
+        assert_eq!("day 25 input is not available yet", err.to_string());
+        let err = client.fetch(2, &dir.join("day02.txt")).unwrap_err();
+        assert_eq!("session token rejected fetching day 2", err.to_string());
+
+        assert!(!dir.join("day25.txt").exists());
+        assert!(!dir.join("day02.txt").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let server = StubServer::start(|_| (200, "R 4\n".into()));
+        let dir = temp_dir("fetch-limit");
+        let client = client(&server.url, &dir, Duration::from_millis(300));
+
+        let start = Instant::now();
+        client.fetch(1, &dir.join("day01.txt")).unwrap();
+        client.fetch(2, &dir.join("day02.txt")).unwrap();
+        client.fetch(3, &dir.join("day03.txt")).unwrap();
+
+        assert!(start.elapsed() >= Duration::from_millis(600));
+        assert_eq!(3, server.requests().len());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
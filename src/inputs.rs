@@ -0,0 +1,208 @@
+//! Encryption of puzzle inputs at rest.
+//!
+//! Puzzle authors ask that inputs aren't published, so `aoc inputs lock` encrypts them in place
+//! with ChaCha20-Poly1305 under a key kept in a local, untracked key file. Locked files start with
+//! a magic header, and [`read`] decrypts them transparently. Unlocked files are read as-is.
+
+use anyhow::{anyhow, Context, Result};
+use chacha20poly1305::aead::{Aead, KeyInit, OsRng};
+use chacha20poly1305::{AeadCore, ChaCha20Poly1305, Nonce};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Cursor};
+use std::path::{Path, PathBuf};
+
+const MAGIC: &[u8] = b"aoc22-locked\n";
+const NONCE_LEN: usize = 12;
+
+pub struct Key(chacha20poly1305::Key);
+
+impl Key {
+    pub fn generate() -> Self {
+        Self(ChaCha20Poly1305::generate_key(&mut OsRng))
+    }
+
+    /// Loads a key stored as 64 hex digits.
+    pub fn load(path: &Path) -> Result<Self> {
+        let hex = std::fs::read_to_string(path)
+            .context(format!("could not read key file '{}'", path.display()))?;
+        let bytes = decode_hex(hex.trim())
+            .context(format!("key file '{}' is not valid", path.display()))?;
+        Ok(Self(*chacha20poly1305::Key::from_slice(&bytes)))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let hex: String = self.0.iter().map(|b| format!("{:02x}", b)).collect();
+        std::fs::write(path, hex + "\n")
+            .context(format!("could not write key file '{}'", path.display()))
+    }
+
+    fn cipher(&self) -> ChaCha20Poly1305 {
+        ChaCha20Poly1305::new(&self.0)
+    }
+}
+
+fn decode_hex(hex: &str) -> Result<Vec<u8>> {
+    if hex.len() != 64 || !hex.is_ascii() {
+        return Err(anyhow!("expected 64 hex digits"));
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|idx| u8::from_str_radix(&hex[idx..idx + 2], 16).map_err(Into::into))
+        .collect()
+}
+
+pub fn is_locked(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+pub fn lock(plain: &[u8], key: &Key) -> Result<Vec<u8>> {
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = key
+        .cipher()
+        .encrypt(&nonce, plain)
+        .map_err(|_| anyhow!("encryption failed"))?;
+
+    Ok([MAGIC, nonce.as_slice(), &ciphertext].concat())
+}
+
+pub fn unlock(bytes: &[u8], key: &Key) -> Result<Vec<u8>> {
+    let body = bytes.strip_prefix(MAGIC).context("input is not locked")?;
+    if body.len() < NONCE_LEN {
+        return Err(anyhow!("locked input is truncated"));
+    }
+    let (nonce, ciphertext) = body.split_at(NONCE_LEN);
+
+    key.cipher()
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| anyhow!("wrong key or corrupted input"))
+}
+
+/// Reads an input, decrypting it with the key in `key_file` if it is locked.
+pub fn read(path: &Path, key_file: &Path) -> Result<String> {
+    let bytes =
+        std::fs::read(path).context(format!("could not read input '{}'", path.display()))?;
+    if !is_locked(&bytes) {
+        return String::from_utf8(bytes)
+            .context(format!("input '{}' is not valid UTF-8", path.display()));
+    }
+
+    if !key_file.is_file() {
+        return Err(anyhow!(
+            "input '{}' is encrypted, but the key file '{}' is missing",
+            path.display(),
+            key_file.display()
+        ));
+    }
+    let key = Key::load(key_file)?;
+    let plain = unlock(&bytes, &key).context(format!("could not decrypt '{}'", path.display()))?;
+    String::from_utf8(plain).context(format!("input '{}' is not valid UTF-8", path.display()))
+}
+
+/// Opens an input for streaming. Unlocked files are read lazily; locked ones can only be decrypted
+/// whole, so they are read into memory as [`read`] would.
+pub fn open(path: &Path, key_file: &Path) -> Result<Box<dyn BufRead>> {
+    let file = File::open(path).context(format!("could not read input '{}'", path.display()))?;
+    let mut reader = BufReader::new(file);
+    let head = reader
+        .fill_buf()
+        .context(format!("could not read input '{}'", path.display()))?;
+
+    if is_locked(head) {
+        Ok(Box::new(Cursor::new(read(path, key_file)?)))
+    } else {
+        Ok(Box::new(reader))
+    }
+}
+
+/// The puzzle inputs (`dayNN.txt`) in `dir`, leaving the example fixtures alone.
+fn puzzle_inputs(dir: &Path) -> Result<Vec<PathBuf>> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"^day\d{2}\.txt$").unwrap();
+    }
+
+    let mut paths = vec![];
+    for entry in std::fs::read_dir(dir).context(format!("could not list '{}'", dir.display()))? {
+        let path = entry?.path();
+        let name = path.file_name().and_then(|name| name.to_str());
+        if name.is_some_and(|name| RE.is_match(name)) {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/// Encrypts every unlocked puzzle input in `dir`, returning the files changed.
+pub fn lock_dir(dir: &Path, key: &Key) -> Result<Vec<PathBuf>> {
+    let mut changed = vec![];
+    for path in puzzle_inputs(dir)? {
+        let bytes = std::fs::read(&path)?;
+        if !is_locked(&bytes) {
+            std::fs::write(&path, lock(&bytes, key)?)
+                .context(format!("could not write '{}'", path.display()))?;
+            changed.push(path);
+        }
+    }
+    Ok(changed)
+}
+
+/// Decrypts every locked puzzle input in `dir`, returning the files changed.
+pub fn unlock_dir(dir: &Path, key: &Key) -> Result<Vec<PathBuf>> {
+    let mut changed = vec![];
+    for path in puzzle_inputs(dir)? {
+        let bytes = std::fs::read(&path)?;
+        if is_locked(&bytes) {
+            let plain =
+                unlock(&bytes, key).context(format!("could not decrypt '{}'", path.display()))?;
+            std::fs::write(&path, plain)
+                .context(format!("could not write '{}'", path.display()))?;
+            changed.push(path);
+        }
+    }
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let key = Key::generate();
+        let locked = lock(b"R 4\nU 4\n", &key).unwrap();
+
+        assert!(is_locked(&locked));
+        assert_eq!(b"R 4\nU 4\n".to_vec(), unlock(&locked, &key).unwrap());
+        assert!(unlock(&locked, &Key::generate()).is_err());
+        assert!(unlock(&locked[..MAGIC.len() + 4], &key).is_err());
+    }
+
+    #[test]
+    fn test_read() {
+        let dir = std::env::temp_dir().join(format!("aoc22-inputs-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (input, example, key_file) = (
+            dir.join("day01.txt"),
+            dir.join("day01-ex.txt"),
+            dir.join("key"),
+        );
+        std::fs::write(&input, "1000\n2000\n").unwrap();
+        std::fs::write(&example, "1000\n").unwrap();
+
+        let key = Key::generate();
+        assert_eq!(vec![input.clone()], lock_dir(&dir, &key).unwrap());
+        assert!(!is_locked(&std::fs::read(&example).unwrap()));
+
+        let err = read(&input, &key_file).unwrap_err();
+        assert!(err.to_string().contains("key file"));
+
+        key.save(&key_file).unwrap();
+        assert_eq!("1000\n2000\n", read(&input, &key_file).unwrap());
+        assert_eq!(vec![input.clone()], unlock_dir(&dir, &key).unwrap());
+        assert_eq!("1000\n2000\n", std::fs::read_to_string(&input).unwrap());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,416 @@
+//! Private leaderboards, read from the JSON export on the site's leaderboard page.
+//!
+//! The parser is strict: unknown fields, malformed keys and inconsistent star data are all errors,
+//! since a half-understood export would quietly give wrong standings.
+
+use crate::output::Format;
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leaderboard {
+    pub owner_id: u64,
+    pub year: i64,
+    pub members: Vec<Member>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub local_score: u64,
+    pub global_score: u64,
+    pub stars: usize,
+    pub last_star_ts: i64,
+    /// When each star was earned, keyed by day.
+    pub days: BTreeMap<u32, Stars>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stars {
+    pub first: i64,
+    pub second: Option<i64>,
+}
+
+impl Member {
+    /// The name shown on the site, which falls back to the id for anonymous users.
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+}
+
+// The export as it appears on the wire, before any checking
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawLeaderboard {
+    owner_id: u64,
+    event: String,
+    #[serde(default)]
+    day1_ts: Option<i64>,
+    members: BTreeMap<String, RawMember>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawMember {
+    id: u64,
+    name: Option<String>,
+    local_score: u64,
+    global_score: u64,
+    stars: usize,
+    last_star_ts: i64,
+    completion_day_level: BTreeMap<String, BTreeMap<String, RawStar>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawStar {
+    get_star_ts: i64,
+    #[allow(dead_code)]
+    star_index: u64,
+}
+
+impl FromStr for Leaderboard {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let raw: RawLeaderboard = serde_json::from_str(s)?;
+        let year = raw
+            .event
+            .parse()
+            .ok()
+            .filter(|&year| year >= 2015)
+            .ok_or_else(|| anyhow!("'{}' is not a valid event", raw.event))?;
+        if let Some(day1_ts) = raw.day1_ts {
+            if day1_ts != unlock_ts(year, 1) {
+                return Err(anyhow!("day1_ts does not match event {}", year));
+            }
+        }
+
+        let members = raw
+            .members
+            .into_iter()
+            .map(|(key, member)| {
+                parse_member(year, &key, member).context(format!("invalid member '{}'", key))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if !members.iter().any(|member| member.id == raw.owner_id) {
+            return Err(anyhow!("owner {} is not a member", raw.owner_id));
+        }
+
+        Ok(Self {
+            owner_id: raw.owner_id,
+            year,
+            members,
+        })
+    }
+}
+
+fn parse_member(year: i64, key: &str, raw: RawMember) -> Result<Member> {
+    if key != raw.id.to_string() {
+        return Err(anyhow!("key does not match id {}", raw.id));
+    }
+
+    let mut days = BTreeMap::new();
+    for (day, levels) in raw.completion_day_level {
+        let day: u32 = day
+            .parse()
+            .ok()
+            .filter(|day| (1..=25).contains(day))
+            .ok_or_else(|| anyhow!("'{}' is not a valid day", day))?;
+
+        let star = |level: &str| -> Result<Option<i64>> {
+            match levels.get(level) {
+                Some(star) if star.get_star_ts < unlock_ts(year, day) => Err(anyhow!(
+                    "day {} star {} earned before the puzzle unlocked",
+                    day,
+                    level
+                )),
+                Some(star) => Ok(Some(star.get_star_ts)),
+                None => Ok(None),
+            }
+        };
+        let stars = match (star("1")?, star("2")?) {
+            (Some(first), Some(second)) if second < first => {
+                return Err(anyhow!("day {} star 2 earned before star 1", day))
+            }
+            (Some(first), second) => Stars { first, second },
+            (None, _) => return Err(anyhow!("day {} has no first star", day)),
+        };
+        if let Some(level) = levels.keys().find(|&level| level != "1" && level != "2") {
+            return Err(anyhow!("'{}' is not a valid star on day {}", level, day));
+        }
+        days.insert(day, stars);
+    }
+
+    let count: usize = days.values().map(|s| 1 + s.second.is_some() as usize).sum();
+    if count != raw.stars {
+        return Err(anyhow!("claims {} stars but has {}", raw.stars, count));
+    }
+    let last = days
+        .values()
+        .flat_map(|s| [Some(s.first), s.second])
+        .flatten()
+        .max()
+        .unwrap_or(0);
+    if last != raw.last_star_ts {
+        return Err(anyhow!(
+            "last_star_ts is {} but the last star is {}",
+            raw.last_star_ts,
+            last
+        ));
+    }
+
+    Ok(Member {
+        id: raw.id,
+        name: raw.name,
+        local_score: raw.local_score,
+        global_score: raw.global_score,
+        stars: raw.stars,
+        last_star_ts: raw.last_star_ts,
+        days,
+    })
+}
+
+/// When a day's puzzle unlocks: midnight US Eastern (UTC-5) on that day of December.
+pub fn unlock_ts(year: i64, day: u32) -> i64 {
+    // Days since the epoch for the given civil date, after Howard Hinnant's `days_from_civil`
+    let (y, m, d) = (year, 12, day as i64);
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * (m - 3) + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146097 + doe - 719468;
+
+    days * 86400 + 5 * 3600
+}
+
+impl Leaderboard {
+    /// Members by local score, ties going to whoever got their last star first.
+    pub fn standings(&self) -> Vec<&Member> {
+        let mut members: Vec<_> = self.members.iter().collect();
+        members.sort_by_key(|m| (std::cmp::Reverse(m.local_score), m.last_star_ts, m.id));
+        members
+    }
+
+    /// Local scores counting only the stars for days `1..=day`. Each star is worth one point per
+    /// member, less one for everyone who got it earlier.
+    pub fn scores_through(&self, day: u32) -> BTreeMap<u64, u64> {
+        let mut scores: BTreeMap<_, _> = self.members.iter().map(|m| (m.id, 0)).collect();
+        let n = self.members.len() as u64;
+
+        for day in 1..=day {
+            for second in [false, true] {
+                let mut times: Vec<_> = self
+                    .members
+                    .iter()
+                    .filter_map(|m| {
+                        let stars = m.days.get(&day)?;
+                        let ts = if second { stars.second? } else { stars.first };
+                        Some((ts, m.id))
+                    })
+                    .collect();
+                times.sort();
+                for (place, (_, id)) in times.into_iter().enumerate() {
+                    *scores.entry(id).or_default() += n - place as u64;
+                }
+            }
+        }
+        scores
+    }
+
+    /// Each member's rank after every day that has stars, keyed by member id. Tied members share
+    /// a rank.
+    pub fn rank_history(&self) -> BTreeMap<u64, Vec<usize>> {
+        let last_day = self
+            .members
+            .iter()
+            .filter_map(|m| m.days.keys().max().copied())
+            .max()
+            .unwrap_or(0);
+
+        let mut history: BTreeMap<_, _> = self.members.iter().map(|m| (m.id, vec![])).collect();
+        for day in 1..=last_day {
+            let scores = self.scores_through(day);
+            for (id, score) in scores.iter() {
+                let rank = 1 + scores.values().filter(|&other| other > score).count();
+                history.entry(*id).or_default().push(rank);
+            }
+        }
+        history
+    }
+
+    pub fn render(&self, format: Format) -> Result<String> {
+        match format {
+            Format::Text => Ok(self.render_text()),
+            Format::Json => {
+                let history = self.rank_history();
+                let members: Vec<_> = self
+                    .standings()
+                    .into_iter()
+                    .map(|m| {
+                        let days: Vec<_> = m
+                            .days
+                            .iter()
+                            .map(|(&day, stars)| {
+                                serde_json::json!({
+                                    "day": day,
+                                    "first": stars.first,
+                                    "second": stars.second,
+                                    "between": stars.second.map(|second| second - stars.first),
+                                })
+                            })
+                            .collect();
+                        serde_json::json!({
+                            "id": m.id,
+                            "name": m.display_name(),
+                            "score": m.local_score,
+                            "stars": m.stars,
+                            "days": days,
+                            "ranks": history[&m.id],
+                        })
+                    })
+                    .collect();
+                Ok(serde_json::to_string(&members)?)
+            }
+        }
+    }
+
+    fn render_text(&self) -> String {
+        let standings = self.standings();
+        let history = self.rank_history();
+        let width = standings
+            .iter()
+            .map(|m| m.display_name().len())
+            .max()
+            .unwrap_or(0);
+        let mut lines = vec![format!("Standings ({})", self.year)];
+
+        for (place, m) in standings.iter().enumerate() {
+            lines.push(format!(
+                "{:>3}) {:>5} {:>2}* {}",
+                place + 1,
+                m.local_score,
+                m.stars,
+                m.display_name()
+            ));
+        }
+
+        lines.push(String::new());
+        lines.push("Stars (time after unlock, then from star 1 to star 2)".into());
+        for m in standings.iter().filter(|m| !m.days.is_empty()) {
+            lines.push(m.display_name());
+            for (&day, stars) in m.days.iter() {
+                let unlock = unlock_ts(self.year, day);
+                let mut line = format!("  day {:>2}  {}", day, hms(stars.first - unlock));
+                if let Some(second) = stars.second {
+                    line += &format!("  {}  +{}", hms(second - unlock), hms(second - stars.first));
+                }
+                lines.push(line);
+            }
+        }
+
+        lines.push(String::new());
+        lines.push("Rank after each day".into());
+        for m in standings.iter() {
+            let ranks: Vec<_> = history[&m.id].iter().map(|r| format!("{:>2}", r)).collect();
+            lines.push(format!(
+                "{:<width$}  {}",
+                m.display_name(),
+                ranks.join(" "),
+                width = width
+            ));
+        }
+
+        lines.join("\n")
+    }
+}
+
+fn hms(secs: i64) -> String {
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("../data/leaderboard/sample.json");
+    const SECOND_STAR_ONLY: &str = include_str!("../data/leaderboard/second-star-only.json");
+
+    #[test]
+    fn test_parse() {
+        let leaderboard: Leaderboard = SAMPLE.parse().unwrap();
+
+        assert_eq!(2022, leaderboard.year);
+        assert_eq!(4, leaderboard.members.len());
+        let bob = &leaderboard.members[1];
+        assert_eq!("bob", bob.display_name());
+        assert_eq!(
+            Some(&Stars {
+                first: 1669871000,
+                second: Some(1669871700)
+            }),
+            bob.days.get(&1)
+        );
+        assert_eq!(
+            "(anonymous user #1003)",
+            leaderboard.members[2].display_name()
+        );
+    }
+
+    #[test]
+    fn test_parse_strict() {
+        let err = SECOND_STAR_ONLY.parse::<Leaderboard>().unwrap_err();
+        assert_eq!("invalid member '1001'", err.to_string());
+        assert_eq!("day 3 has no first star", err.root_cause().to_string());
+
+        let unknown = SAMPLE.replacen("\"owner_id\"", "\"extra\": 1, \"owner_id\"", 1);
+        assert!(unknown.parse::<Leaderboard>().is_err());
+        let miscounted = SAMPLE.replacen("\"stars\": 6", "\"stars\": 7", 1);
+        assert!(miscounted.parse::<Leaderboard>().is_err());
+        let bad_event = SAMPLE.replacen("\"2022\"", "\"next year\"", 1);
+        assert!(bad_event.parse::<Leaderboard>().is_err());
+    }
+
+    #[test]
+    fn test_scores_and_ranks() {
+        let leaderboard: Leaderboard = SAMPLE.parse().unwrap();
+
+        // The recomputed scores agree with the export once every day is counted
+        let scores = leaderboard.scores_through(25);
+        for member in leaderboard.members.iter() {
+            assert_eq!(member.local_score, scores[&member.id]);
+        }
+
+        let history = leaderboard.rank_history();
+        assert_eq!(vec![1, 1, 2], history[&1001]);
+        assert_eq!(vec![1, 1, 1], history[&1002]);
+        assert_eq!(vec![3, 3, 3], history[&1003]);
+        assert_eq!(vec![4, 4, 4], history[&1004]);
+    }
+
+    #[test]
+    fn test_render() {
+        let leaderboard: Leaderboard = SAMPLE.parse().unwrap();
+        let text = leaderboard.render(Format::Text).unwrap();
+        let lines: Vec<_> = text.lines().collect();
+
+        assert_eq!("Standings (2022)", lines[0]);
+        assert_eq!("  1)    22  6* bob", lines[1]);
+        assert!(text.contains("  day  1  00:03:20  00:15:00  +00:11:40"));
+        assert!(text.contains("  day  3  25:00:00"));
+        assert_eq!(Some(&"carol                    4  4  4"), lines.last());
+    }
+
+    #[test]
+    fn test_unlock_ts() {
+        assert_eq!(1669870800, unlock_ts(2022, 1));
+        assert_eq!(1671944400, unlock_ts(2022, 25));
+    }
+}
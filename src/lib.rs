@@ -1,4 +1,38 @@
-use std::time::Instant;
+use std::time::{Duration, Instant};
+
+pub mod answers;
+pub mod cli;
+pub mod collections;
+pub mod config;
+pub mod days;
+pub mod detect;
+pub mod fetch;
+pub mod inputs;
+pub mod leaderboard;
+pub mod lint;
+pub mod output;
+#[cfg(feature = "python")]
+mod python;
+pub mod runner;
+#[cfg(all(
+    test,
+    any(
+        feature = "day01",
+        feature = "day02",
+        feature = "day03",
+        feature = "day04",
+        feature = "day05",
+        feature = "day06",
+        feature = "day07",
+        feature = "day08",
+        feature = "day09"
+    )
+))]
+mod snapshot;
+pub mod status;
+#[cfg(test)]
+mod stub;
+pub mod submit;
 
 pub struct Timer(Instant);
 
@@ -8,6 +42,10 @@ impl Timer {
     }
 
     pub fn tock(&self) {
-        println!("{} ms", (Instant::now() - self.0).as_millis())
+        println!("{} ms", self.elapsed().as_millis())
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        Instant::now() - self.0
     }
 }
@@ -0,0 +1,384 @@
+//! Structural checks for puzzle inputs.
+//!
+//! The solutions make assumptions about their input that they never check (one digit per tree,
+//! even length rucksacks, fixed-width stack diagrams...). These checks run over an input up front
+//! and report every line that breaks one of those assumptions.
+
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub line: usize,
+    pub message: String,
+}
+
+impl Violation {
+    pub fn new(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Checks `input` against the assumptions made by the given day's solution. Line numbers in the
+/// returned violations are 1-based.
+pub fn lint(day: u32, input: &str) -> Result<Vec<Violation>> {
+    match day {
+        1 => Ok(day01(input)),
+        2 => Ok(day02(input)),
+        3 => Ok(day03(input)),
+        4 => Ok(day04(input)),
+        5 => Ok(day05(input)),
+        6 => Ok(day06(input)),
+        7 => Ok(day07(input)),
+        8 => Ok(day08(input)),
+        9 => Ok(day09(input)),
+        _ => Err(anyhow!("no lint rules for day {}", day)),
+    }
+}
+
+// Numbered lines, starting from 1
+fn numbered(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input.lines().enumerate().map(|(idx, line)| (idx + 1, line))
+}
+
+fn day01(input: &str) -> Vec<Violation> {
+    numbered(input)
+        .filter(|(_, line)| !line.is_empty() && line.parse::<u32>().is_err())
+        .map(|(n, line)| Violation::new(n, format!("'{}' is not a calorie count", line)))
+        .collect()
+}
+
+fn day02(input: &str) -> Vec<Violation> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"^[ABC] [XYZ]$").unwrap();
+    }
+
+    numbered(input)
+        .filter(|(_, line)| !RE.is_match(line))
+        .map(|(n, line)| Violation::new(n, format!("'{}' is not of the form '[ABC] [XYZ]'", line)))
+        .collect()
+}
+
+fn day03(input: &str) -> Vec<Violation> {
+    let mut violations = vec![];
+
+    for (n, line) in numbered(input) {
+        if let Some(c) = line.chars().find(|c| !c.is_ascii_alphabetic()) {
+            violations.push(Violation::new(n, format!("'{}' is not an item type", c)));
+        } else if line.is_empty() || !line.len().is_multiple_of(2) {
+            violations.push(Violation::new(
+                n,
+                format!(
+                    "rucksack of length {} can't be split into compartments",
+                    line.len()
+                ),
+            ));
+        }
+    }
+
+    violations
+}
+
+fn day04(input: &str) -> Vec<Violation> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"^(\d+)-(\d+),(\d+)-(\d+)$").unwrap();
+    }
+
+    let mut violations = vec![];
+
+    for (n, line) in numbered(input) {
+        let caps = match RE.captures(line) {
+            Some(caps) => caps,
+            None => {
+                violations.push(Violation::new(
+                    n,
+                    format!("'{}' is not of the form 'a-b,c-d'", line),
+                ));
+                continue;
+            }
+        };
+
+        let bounds: Vec<_> = (1..=4).map(|i| caps[i].parse::<u32>()).collect();
+        match &bounds[..] {
+            [Ok(a), Ok(b), Ok(c), Ok(d)] => {
+                if a > b || c > d {
+                    violations.push(Violation::new(
+                        n,
+                        format!("'{}' has a backwards range", line),
+                    ));
+                }
+            }
+            _ => violations.push(Violation::new(
+                n,
+                format!("'{}' has an out-of-range section", line),
+            )),
+        }
+    }
+
+    violations
+}
+
+fn day05(input: &str) -> Vec<Violation> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap();
+    }
+
+    let mut violations = vec![];
+    let lines: Vec<_> = numbered(input).collect();
+
+    let split = match lines.iter().position(|(_, line)| line.is_empty()) {
+        Some(split) => split,
+        None => {
+            violations.push(Violation::new(
+                lines.len() + 1,
+                "no blank line between stack diagram and moves",
+            ));
+            return violations;
+        }
+    };
+    let (diagram, moves) = (&lines[..split], &lines[split + 1..]);
+
+    // The names row is the last line of the diagram. Each name must be a single character sitting
+    // in the same column as the crates above it.
+    let (names_n, names) = match diagram.last() {
+        Some(&names) => names,
+        None => {
+            violations.push(Violation::new(1, "missing stack diagram"));
+            return violations;
+        }
+    };
+    let mut stacks = 0;
+    for (col, c) in names.chars().enumerate() {
+        match (col % 4, c) {
+            (1, '1'..='9') if c.to_digit(10) == Some(stacks + 1) => stacks += 1,
+            (1, _) => violations.push(Violation::new(
+                names_n,
+                format!(
+                    "expected stack name '{}' at column {}, found '{}'",
+                    stacks + 1,
+                    col + 1,
+                    c
+                ),
+            )),
+            (_, ' ') => {}
+            (_, _) => violations.push(Violation::new(
+                names_n,
+                format!("unexpected '{}' at column {} in stack names", c, col + 1),
+            )),
+        }
+    }
+
+    // Every crate row is a sequence of `[X] ` cells (or blanks), one per stack column
+    for &(n, line) in &diagram[..diagram.len() - 1] {
+        for (col, c) in line.chars().enumerate() {
+            let ok = match col % 4 {
+                0 => c == '[' || c == ' ',
+                1 => c.is_ascii_alphanumeric() || c == ' ',
+                2 => c == ']' || c == ' ',
+                _ => c == ' ',
+            };
+            if !ok {
+                violations.push(Violation::new(
+                    n,
+                    format!(
+                        "'{}' at column {} breaks the one-crate-per-column layout",
+                        c,
+                        col + 1
+                    ),
+                ));
+            } else if col % 4 == 1 && c != ' ' && col / 4 >= stacks as usize {
+                violations.push(Violation::new(
+                    n,
+                    format!("crate '{}' at column {} has no stack below it", c, col + 1),
+                ));
+            }
+        }
+    }
+
+    for &(n, line) in moves {
+        let caps = match RE.captures(line) {
+            Some(caps) => caps,
+            None => {
+                violations.push(Violation::new(
+                    n,
+                    format!("'{}' is not of the form 'move n from a to b'", line),
+                ));
+                continue;
+            }
+        };
+
+        for idx in [2, 3] {
+            match caps[idx].parse::<u32>() {
+                Ok(stack) if (1..=stacks).contains(&stack) => {}
+                _ => violations.push(Violation::new(
+                    n,
+                    format!("stack '{}' does not exist", &caps[idx]),
+                )),
+            }
+        }
+    }
+
+    violations
+}
+
+fn day06(input: &str) -> Vec<Violation> {
+    let mut violations = vec![];
+
+    for (n, line) in numbered(input) {
+        if n > 1 {
+            violations.push(Violation::new(n, "datastream must be a single line"));
+        }
+        if let Some((col, c)) = line.chars().enumerate().find(|(_, c)| !c.is_ascii()) {
+            violations.push(Violation::new(
+                n,
+                format!("non-ASCII character '{}' at column {}", c, col + 1),
+            ));
+        }
+    }
+
+    violations
+}
+
+fn day07(input: &str) -> Vec<Violation> {
+    let mut violations = vec![];
+    let mut listing = false;
+
+    for (n, line) in numbered(input) {
+        let tokens: Vec<_> = line.split_ascii_whitespace().collect();
+        match &tokens[..] {
+            ["$", "ls"] => listing = true,
+            ["$", "cd", _] => listing = false,
+            ["$", ..] => {
+                listing = false;
+                violations.push(Violation::new(n, format!("unknown command '{}'", line)));
+            }
+            ["dir", _] if listing => {}
+            [size, _] if listing => {
+                if size.parse::<usize>().is_err() {
+                    violations.push(Violation::new(n, format!("invalid file size '{}'", size)));
+                }
+            }
+            [_, _] => violations.push(Violation::new(
+                n,
+                format!("'{}' is not part of an 'ls' listing", line),
+            )),
+            _ => violations.push(Violation::new(
+                n,
+                format!("invalid filesystem entry '{}'", line),
+            )),
+        }
+    }
+
+    violations
+}
+
+fn day08(input: &str) -> Vec<Violation> {
+    let mut violations = vec![];
+    let mut width = None;
+
+    for (n, line) in numbered(input) {
+        if let Some((col, c)) = line.chars().enumerate().find(|(_, c)| !c.is_ascii_digit()) {
+            violations.push(Violation::new(
+                n,
+                format!("'{}' at column {} is not a tree height", c, col + 1),
+            ));
+        }
+
+        match width {
+            None => width = Some(line.len()),
+            Some(width) if width != line.len() => violations.push(Violation::new(
+                n,
+                format!("row has length {}, expected {}", line.len(), width),
+            )),
+            Some(_) => {}
+        }
+    }
+
+    violations
+}
+
+fn day09(input: &str) -> Vec<Violation> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"^[LRUD] (\d+)$").unwrap();
+    }
+
+    numbered(input)
+        .filter(|(_, line)| {
+            RE.captures(line)
+                .is_none_or(|caps| caps[1].parse::<u32>().is_err())
+        })
+        .map(|(n, line)| Violation::new(n, format!("'{}' is not of the form '[LRUD] n'", line)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(violations: &[Violation]) -> Vec<usize> {
+        violations.iter().map(|v| v.line).collect()
+    }
+
+    #[test]
+    fn test_inputs_are_clean() {
+        let inputs: [&[u8]; 9] = [
+            include_bytes!("../data/day01.txt"),
+            include_bytes!("../data/day02.txt"),
+            include_bytes!("../data/day03.txt"),
+            include_bytes!("../data/day04.txt"),
+            include_bytes!("../data/day05.txt"),
+            include_bytes!("../data/day06.txt"),
+            include_bytes!("../data/day07.txt"),
+            include_bytes!("../data/day08.txt"),
+            include_bytes!("../data/day09.txt"),
+        ];
+
+        // Locked inputs can't be checked without the key
+        for (day, input) in (1..)
+            .zip(inputs)
+            .filter(|(_, i)| !crate::inputs::is_locked(i))
+        {
+            let input = std::str::from_utf8(input).unwrap();
+            assert_eq!(
+                Vec::<Violation>::new(),
+                lint(day, input).unwrap(),
+                "day {}",
+                day
+            );
+        }
+    }
+
+    #[test]
+    fn test_lint_day03() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp\nabc\nab1d\n";
+        assert_eq!(vec![2, 3], lines(&lint(3, input).unwrap()));
+    }
+
+    #[test]
+    fn test_lint_day05() {
+        let input = "    [D]    \n[N] [C]    \n[Z] [M] [P] [Q]\n 1   2   3 \n\nmove 1 from 2 to 1\nmove 1 from 4 to 1\nmove one from 1 to 2\n";
+        assert_eq!(vec![3, 7, 8], lines(&lint(5, input).unwrap()));
+    }
+
+    #[test]
+    fn test_lint_day08() {
+        let input = "30373\n25a12\n6533\n";
+        let violations = lint(8, input).unwrap();
+        assert_eq!(vec![2, 3], lines(&violations));
+        assert_eq!(
+            "line 2: 'a' at column 3 is not a tree height",
+            violations[0].to_string()
+        );
+    }
+}
@@ -0,0 +1,228 @@
+//! Rendering of run results as plain text or JSON.
+
+use anyhow::{anyhow, Error, Result};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(anyhow!("'{}' is not a valid output format", s)),
+        }
+    }
+}
+
+/// Unit used when reporting elapsed time.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Precision {
+    S,
+    #[default]
+    Ms,
+    Us,
+    Ns,
+}
+
+impl Precision {
+    pub fn unit(&self) -> &'static str {
+        match self {
+            Precision::S => "s",
+            Precision::Ms => "ms",
+            Precision::Us => "us",
+            Precision::Ns => "ns",
+        }
+    }
+
+    pub fn measure(&self, elapsed: Duration) -> u128 {
+        match self {
+            Precision::S => elapsed.as_secs() as u128,
+            Precision::Ms => elapsed.as_millis(),
+            Precision::Us => elapsed.as_micros(),
+            Precision::Ns => elapsed.as_nanos(),
+        }
+    }
+}
+
+impl FromStr for Precision {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "s" => Ok(Precision::S),
+            "ms" => Ok(Precision::Ms),
+            "us" => Ok(Precision::Us),
+            "ns" => Ok(Precision::Ns),
+            _ => Err(anyhow!("'{}' is not a valid timer precision", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Answer {
+    pub part: usize,
+    pub answer: String,
+}
+
+/// The answers from running one day, and how long they took.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub day: u32,
+    pub answers: Vec<Answer>,
+    pub elapsed: Duration,
+}
+
+impl Report {
+    pub fn render(&self, format: Format, precision: Precision) -> Result<String> {
+        match format {
+            Format::Text => {
+                let mut lines: Vec<_> = self.answers.iter().map(|a| a.answer.clone()).collect();
+                lines.push(format!(
+                    "{} {}",
+                    precision.measure(self.elapsed),
+                    precision.unit()
+                ));
+                Ok(lines.join("\n"))
+            }
+            Format::Json => {
+                let json = serde_json::json!({
+                    "day": self.day,
+                    "answers": self.answers,
+                    "elapsed": precision.measure(self.elapsed) as u64,
+                    "unit": precision.unit(),
+                });
+                Ok(serde_json::to_string(&json)?)
+            }
+        }
+    }
+}
+
+/// Reports from running every variant of a day on the same input, the main implementation first.
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub day: u32,
+    pub runs: Vec<(String, Report)>,
+}
+
+impl Comparison {
+    pub fn render(&self, format: Format, precision: Precision) -> Result<String> {
+        match format {
+            Format::Text => {
+                let width = self.runs.iter().map(|(name, _)| name.len()).max();
+                let baseline = self.runs.first().map(|(_, report)| report.elapsed);
+
+                let mut lines: Vec<_> = self
+                    .runs
+                    .iter()
+                    .map(|(name, report)| {
+                        let answers: Vec<_> =
+                            report.answers.iter().map(|a| &a.answer[..]).collect();
+                        let speedup = baseline.unwrap_or_default().as_secs_f64()
+                            / report.elapsed.as_secs_f64().max(f64::MIN_POSITIVE);
+                        format!(
+                            "{:<width$}  {:>8} {}  {:>6.2}x  {}",
+                            name,
+                            precision.measure(report.elapsed),
+                            precision.unit(),
+                            speedup,
+                            answers.join(" "),
+                            width = width.unwrap_or(0)
+                        )
+                    })
+                    .collect();
+                lines.push(match self.runs.len() {
+                    1 => "only one variant, nothing to compare".into(),
+                    n => format!("all {} variants agree", n),
+                });
+                Ok(lines.join("\n"))
+            }
+            Format::Json => {
+                let variants: Vec<_> = self
+                    .runs
+                    .iter()
+                    .map(|(name, report)| {
+                        serde_json::json!({
+                            "variant": name,
+                            "answers": report.answers,
+                            "elapsed": precision.measure(report.elapsed) as u64,
+                        })
+                    })
+                    .collect();
+                let json = serde_json::json!({
+                    "day": self.day,
+                    "variants": variants,
+                    "unit": precision.unit(),
+                });
+                Ok(serde_json::to_string(&json)?)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let report = Report {
+            day: 6,
+            answers: vec![
+                Answer {
+                    part: 1,
+                    answer: "7".into(),
+                },
+                Answer {
+                    part: 2,
+                    answer: "19".into(),
+                },
+            ],
+            elapsed: Duration::from_micros(1500),
+        };
+
+        assert_eq!(
+            "7\n19\n1 ms",
+            report.render(Format::Text, Precision::Ms).unwrap()
+        );
+        assert_eq!(
+            r#"{"answers":[{"answer":"7","part":1},{"answer":"19","part":2}],"day":6,"elapsed":1500,"unit":"us"}"#,
+            report.render(Format::Json, Precision::Us).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_render_comparison() {
+        let report = |micros| Report {
+            day: 6,
+            answers: vec![Answer {
+                part: 1,
+                answer: "7".into(),
+            }],
+            elapsed: Duration::from_micros(micros),
+        };
+        let comparison = Comparison {
+            day: 6,
+            runs: vec![
+                ("default".into(), report(900)),
+                ("window".into(), report(300)),
+            ],
+        };
+
+        assert_eq!(
+            "default       900 us    1.00x  7\nwindow        300 us    3.00x  7\nall 2 variants agree",
+            comparison.render(Format::Text, Precision::Us).unwrap()
+        );
+    }
+}
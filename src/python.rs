@@ -0,0 +1,269 @@
+//! Python bindings, for exploring the puzzles from a notebook.
+//!
+//! Solver errors and invalid inputs raise `ValueError`, unknown or disabled days raise
+//! `LookupError`, and paths or coordinates that don't exist raise `KeyError` and `IndexError`.
+
+use crate::days::day07::fs::FileSystem;
+use crate::days::day08::{Forest, Tree};
+use crate::days::day09::{self, Direction, Motion, Position};
+use crate::runner;
+use pyo3::exceptions::{PyIndexError, PyKeyError, PyLookupError, PyValueError};
+use pyo3::prelude::*;
+
+fn value_error(err: anyhow::Error) -> PyErr {
+    PyValueError::new_err(format!("{:#}", err))
+}
+
+/// Solves both parts of `day` for `input`. The second answer is `None` for days with one part.
+#[pyfunction]
+fn solve(day: u32, input: &str) -> PyResult<(String, Option<String>)> {
+    let day = runner::find(day).map_err(|err| PyLookupError::new_err(format!("{:#}", err)))?;
+    let mut answers = day
+        .parts
+        .iter()
+        .map(|part| part(input))
+        .collect::<anyhow::Result<Vec<_>>>()
+        .map_err(value_error)?
+        .into_iter();
+
+    let first = answers
+        .next()
+        .ok_or_else(|| PyLookupError::new_err(format!("day {} has no parts", day.number)))?;
+    Ok((first, answers.next()))
+}
+
+/// The day 7 filesystem, rebuilt from a terminal session.
+#[pyclass(name = "FileSystem", module = "aoc22")]
+struct PyFileSystem(FileSystem);
+
+#[pymethods]
+impl PyFileSystem {
+    #[new]
+    fn new(input: &str) -> PyResult<Self> {
+        input.parse().map(Self).map_err(value_error)
+    }
+
+    /// Size of the file or folder at an absolute path.
+    #[pyo3(signature = (path = "/"))]
+    fn size(&self, path: &str) -> PyResult<usize> {
+        let idx = self
+            .0
+            .find(path)
+            .ok_or_else(|| PyKeyError::new_err(path.to_string()))?;
+        Ok(self.0.size(idx))
+    }
+
+    fn folder_sizes(&self) -> Vec<(String, usize)> {
+        self.0.folder_sizes()
+    }
+
+    fn __contains__(&self, path: &str) -> bool {
+        self.0.find(path).is_some()
+    }
+}
+
+/// The day 8 forest, as a grid of tree heights.
+#[pyclass(name = "Forest", module = "aoc22")]
+struct PyForest(Forest);
+
+#[pymethods]
+impl PyForest {
+    #[new]
+    fn new(input: &str) -> PyResult<Self> {
+        input.parse().map(Self).map_err(value_error)
+    }
+
+    #[getter]
+    fn width(&self) -> usize {
+        self.0.width()
+    }
+
+    #[getter]
+    fn height(&self) -> usize {
+        self.0.height()
+    }
+
+    /// Height of the tree at column `x`, row `y`.
+    fn tree(&self, x: usize, y: usize) -> PyResult<u8> {
+        self.tree_at(x, y).map(|tree| tree.height)
+    }
+
+    fn scenic_score(&self, x: usize, y: usize) -> PyResult<usize> {
+        let tree = self.tree_at(x, y)?;
+        Ok(self.0.scenic_score(&tree))
+    }
+
+    /// Coordinates of every tree visible from outside the forest, in row order.
+    fn visible(&self) -> Vec<(usize, usize)> {
+        self.0
+            .visible()
+            .iter()
+            .map(|(x, y)| (x as usize, y as usize))
+            .collect()
+    }
+}
+
+impl PyForest {
+    fn tree_at(&self, x: usize, y: usize) -> PyResult<Tree> {
+        self.0
+            .tree(x, y)
+            .ok_or_else(|| PyIndexError::new_err(format!("no tree at ({}, {})", x, y)))
+    }
+}
+
+/// The day 9 rope, with any number of knots.
+#[pyclass(name = "Rope", module = "aoc22")]
+struct PyRope(Vec<Position>);
+
+#[pymethods]
+impl PyRope {
+    #[new]
+    #[pyo3(signature = (knots = 2))]
+    fn new(knots: usize) -> PyResult<Self> {
+        if knots == 0 {
+            return Err(PyValueError::new_err("a rope needs at least one knot"));
+        }
+        Ok(Self(vec![Position::default(); knots]))
+    }
+
+    /// Positions of every knot, head first.
+    #[getter]
+    fn knots(&self) -> Vec<(i32, i32)> {
+        self.0.iter().map(|p| (p.x, p.y)).collect()
+    }
+
+    #[getter]
+    fn tail(&self) -> (i32, i32) {
+        let tail = self.0[self.0.len() - 1];
+        (tail.x, tail.y)
+    }
+
+    /// Moves the head one step: `"L"`, `"R"`, `"U"` or `"D"`.
+    fn step(&mut self, direction: &str) -> PyResult<()> {
+        let dir: Direction = direction.parse().map_err(value_error)?;
+        day09::step_knots(&mut self.0, &dir).map_err(value_error)
+    }
+
+    /// Applies motions in the puzzle format (`"R 4"`, one per line), returning the tail position
+    /// after every step.
+    fn apply(&mut self, motions: &str) -> PyResult<Vec<(i32, i32)>> {
+        let mut tails = vec![];
+        for line in motions.lines() {
+            let motion: Motion = line.parse().map_err(value_error)?;
+            for _ in 0..motion.steps {
+                day09::step_knots(&mut self.0, &motion.dir).map_err(value_error)?;
+                tails.push(self.tail());
+            }
+        }
+        Ok(tails)
+    }
+}
+
+#[pymodule]
+fn aoc22(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(solve, m)?)?;
+    m.add_class::<PyFileSystem>()?;
+    m.add_class::<PyForest>()?;
+    m.add_class::<PyRope>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    fn with_python(f: impl FnOnce(Python<'_>)) {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(f)
+    }
+
+    #[test]
+    fn test_value_error() {
+        with_python(|py| {
+            let err = value_error(anyhow!("bad").context("outer"));
+            assert!(err.is_instance_of::<PyValueError>(py));
+            assert_eq!("outer: bad", err.value(py).to_string());
+        });
+    }
+
+    #[test]
+    fn test_solve() {
+        with_python(|py| {
+            let input = include_str!("../data/day09-ex.txt");
+            assert_eq!(
+                ("13".to_string(), Some("1".to_string())),
+                solve(9, input).unwrap()
+            );
+            assert!(solve(9, "R 4\nX 2\n")
+                .err()
+                .unwrap()
+                .is_instance_of::<PyValueError>(py));
+            assert!(solve(99, input)
+                .err()
+                .unwrap()
+                .is_instance_of::<PyLookupError>(py));
+        });
+    }
+
+    #[test]
+    fn test_file_system() {
+        with_python(|py| {
+            let fs = PyFileSystem::new(include_str!("../data/day07-ex.txt")).unwrap();
+            assert_eq!(48381165, fs.size("/").unwrap());
+            assert!(fs.__contains__("/a/e") && !fs.__contains__("/z"));
+            assert!(fs
+                .size("/z")
+                .err()
+                .unwrap()
+                .is_instance_of::<PyKeyError>(py));
+            assert!(PyFileSystem::new("$ rm -rf /\n")
+                .err()
+                .unwrap()
+                .is_instance_of::<PyValueError>(py));
+        });
+    }
+
+    #[test]
+    fn test_forest() {
+        with_python(|py| {
+            let forest = PyForest::new(include_str!("../data/day08-ex.txt")).unwrap();
+            assert_eq!((5, 5), (forest.width(), forest.height()));
+            assert_eq!(5, forest.tree(2, 1).unwrap());
+            assert_eq!(8, forest.scenic_score(2, 3).unwrap());
+            assert_eq!(21, forest.visible().len());
+            assert!(forest
+                .tree(5, 0)
+                .err()
+                .unwrap()
+                .is_instance_of::<PyIndexError>(py));
+            assert!(PyForest::new("30373\n2551\n")
+                .err()
+                .unwrap()
+                .is_instance_of::<PyValueError>(py));
+        });
+    }
+
+    #[test]
+    fn test_rope() {
+        with_python(|py| {
+            let mut rope = PyRope::new(2).unwrap();
+            let tails = rope.apply("R 2\nU 1\n").unwrap();
+            assert_eq!(vec![(0, 0), (1, 0), (1, 0)], tails);
+            assert!(rope
+                .step("X")
+                .err()
+                .unwrap()
+                .is_instance_of::<PyValueError>(py));
+            assert!(rope
+                .apply("R4\n")
+                .err()
+                .unwrap()
+                .is_instance_of::<PyValueError>(py));
+            assert!(PyRope::new(0)
+                .err()
+                .unwrap()
+                .is_instance_of::<PyValueError>(py));
+        });
+    }
+}
@@ -0,0 +1,476 @@
+//! Registry of the day solvers compiled into this build.
+//!
+//! Each day sits behind a cargo feature of the same name (`day01` through `day25`), so a build
+//! only contains the days it was asked for.
+
+use crate::cli::Options;
+use crate::config::Config;
+#[allow(unused_imports)]
+use crate::days::*;
+use crate::fetch::Client;
+use crate::inputs;
+use crate::output::{Answer, Comparison, Report};
+use crate::status;
+use crate::Timer;
+use anyhow::{anyhow, Context, Result};
+use std::fmt::Display;
+use std::io::{BufRead, Cursor};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc};
+use std::thread;
+
+/// Days that have a solution in the tree, whether or not their feature is enabled.
+pub const SOLVED: &[u32] = &[1, 2, 3, 4, 5, 6, 7, 8, 9];
+
+pub type Part = Arc<dyn Fn(&str) -> Result<String> + Send + Sync>;
+
+/// Name of a day's main implementation, for `--variant`.
+pub const DEFAULT_VARIANT: &str = "default";
+/// Passing this to `--variant` runs and cross-checks every implementation.
+pub const ALL_VARIANTS: &str = "all";
+
+pub struct Day {
+    pub number: u32,
+    pub parts: Vec<Part>,
+    /// Alternative implementations, kept to check and time against the main one.
+    pub variants: Vec<Variant>,
+}
+
+impl Day {
+    pub fn new(number: u32) -> Self {
+        Self {
+            number,
+            parts: vec![],
+            variants: vec![],
+        }
+    }
+
+    pub fn part<T: Display + 'static>(mut self, part: fn(&str) -> Result<T>) -> Self {
+        self.parts.push(boxed(part));
+        self
+    }
+
+    pub fn variant(mut self, variant: Variant) -> Self {
+        self.variants.push(variant);
+        self
+    }
+
+    pub fn variant_names(&self) -> Vec<&'static str> {
+        std::iter::once(DEFAULT_VARIANT)
+            .chain(self.variants.iter().map(|v| v.name))
+            .collect()
+    }
+
+    fn parts_of(&self, variant: Option<&str>) -> Result<&[Part]> {
+        match variant {
+            None | Some(DEFAULT_VARIANT) => Ok(&self.parts),
+            Some(name) => self
+                .variants
+                .iter()
+                .find(|v| v.name == name)
+                .map(|v| &v.parts[..])
+                .ok_or_else(|| {
+                    anyhow!(
+                        "day {} has no variant '{}' (try {})",
+                        self.number,
+                        name,
+                        self.variant_names().join(", ")
+                    )
+                }),
+        }
+    }
+
+    /// Runs the parts selected by `options` against the selected input.
+    ///
+    /// The parts run on a separate thread so a timeout from the config can be enforced. A part that
+    /// times out is abandoned rather than stopped, and keeps running until the process exits.
+    pub fn run(&self, config: &Config, options: &Options) -> Result<Report> {
+        let input = read_input(self.number, config, options)?;
+        let solvers = self.parts_of(options.variant.as_deref())?;
+        let parts: Vec<_> = match options.part {
+            Some(part) => {
+                let solver = solvers
+                    .get(part - 1)
+                    .context(format!("day {} has no part {}", self.number, part))?;
+                vec![(part, solver.clone())]
+            }
+            None => (1..).zip(solvers.iter().cloned()).collect(),
+        };
+
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let timer = Timer::tick();
+            let answers = parts
+                .iter()
+                .map(|(part, solver)| {
+                    solver(&input).map(|answer| Answer {
+                        part: *part,
+                        answer,
+                    })
+                })
+                .collect::<Result<Vec<_>>>();
+            // The receiver is gone if we timed out, in which case nobody cares about the answer
+            let _ = tx.send(answers.map(|answers| (answers, timer.elapsed())));
+        });
+
+        let (answers, elapsed) = match config.timeout(self.number) {
+            Some(timeout) => rx
+                .recv_timeout(timeout)
+                .map_err(|_| anyhow!("day {} timed out after {:?}", self.number, timeout))??,
+            None => rx.recv()??,
+        };
+
+        Ok(Report {
+            day: self.number,
+            answers,
+            elapsed,
+        })
+    }
+
+    /// Runs every variant against the same input, failing unless they all give the same answers.
+    pub fn compare(&self, config: &Config, options: &Options) -> Result<Comparison> {
+        let mut runs = vec![];
+        for name in self.variant_names() {
+            let options = Options {
+                variant: Some(name.into()),
+                ..options.clone()
+            };
+            runs.push((name.to_string(), self.run(config, &options)?));
+        }
+
+        let (expected_name, expected) = &runs[0];
+        for (name, report) in runs.iter().skip(1) {
+            for (want, got) in expected.answers.iter().zip(report.answers.iter()) {
+                if want.answer != got.answer {
+                    return Err(anyhow!(
+                        "day {} variants disagree on part {}: {} gives {}, {} gives {}",
+                        self.number,
+                        want.part,
+                        expected_name,
+                        want.answer,
+                        name,
+                        got.answer
+                    ));
+                }
+            }
+        }
+
+        Ok(Comparison {
+            day: self.number,
+            runs,
+        })
+    }
+}
+
+/// An alternative implementation of a day's parts.
+pub struct Variant {
+    pub name: &'static str,
+    pub parts: Vec<Part>,
+}
+
+impl Variant {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            parts: vec![],
+        }
+    }
+
+    pub fn part<T: Display + 'static>(mut self, part: fn(&str) -> Result<T>) -> Self {
+        self.parts.push(boxed(part));
+        self
+    }
+}
+
+fn boxed<T: Display + 'static>(part: fn(&str) -> Result<T>) -> Part {
+    Arc::new(move |input| part(input).map(|answer| answer.to_string()))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Enabled,
+    Disabled,
+    Unsolved,
+}
+
+impl Status {
+    pub fn of(day: u32) -> Self {
+        if !SOLVED.contains(&day) {
+            Status::Unsolved
+        } else if registry().iter().any(|d| d.number == day) {
+            Status::Enabled
+        } else {
+            Status::Disabled
+        }
+    }
+}
+
+/// All the days enabled in this build, in order.
+#[allow(clippy::vec_init_then_push)]
+pub fn registry() -> Vec<Day> {
+    #[allow(unused_mut)]
+    let mut days = vec![];
+
+    #[cfg(feature = "day01")]
+    days.push(Day::new(1).part(day01::part_one).part(day01::part_two));
+    #[cfg(feature = "day02")]
+    days.push(
+        Day::new(2)
+            .part(day02::part_one)
+            .part(day02::part_two)
+            .variant(
+                Variant::new("bytes")
+                    .part(day02::part_one_bytes)
+                    .part(day02::part_two_bytes),
+            ),
+    );
+    #[cfg(feature = "day03")]
+    days.push(
+        Day::new(3)
+            .part(day03::part_one)
+            .part(day03::part_two)
+            .variant(
+                Variant::new("hashset")
+                    .part(day03::part_one_hashset)
+                    .part(day03::part_two_hashset),
+            ),
+    );
+    #[cfg(feature = "day04")]
+    days.push(Day::new(4).part(day04::part_one).part(day04::part_two));
+    #[cfg(feature = "day05")]
+    days.push(Day::new(5).part(day05::part_one).part(day05::part_two));
+    #[cfg(feature = "day06")]
+    days.push(
+        Day::new(6)
+            .part(day06::part_one)
+            .part(day06::part_two)
+            .variant(
+                Variant::new("window")
+                    .part(day06::part_one_window)
+                    .part(day06::part_two_window),
+            ),
+    );
+    #[cfg(feature = "day07")]
+    days.push(Day::new(7).part(day07::part_one));
+    #[cfg(feature = "day08")]
+    days.push(
+        Day::new(8)
+            .part(day08::part_one)
+            .part(day08::part_two)
+            .variant(
+                Variant::new("stack")
+                    .part(day08::part_one)
+                    .part(day08::part_two_stack),
+            ),
+    );
+    #[cfg(feature = "day09")]
+    days.push(Day::new(9).part(day09::part_one).part(day09::part_two));
+
+    days
+}
+
+/// Entry point for the day binaries: runs `day` with options taken from the command line.
+pub fn main(day: u32) -> Result<()> {
+    let options = Options::from_env()?;
+    let config = Config::load()?.with_options(&options);
+    execute(day, &config, &options)
+}
+
+/// Runs `day` as selected by `options` and prints the report, or with `--variant all` runs and
+/// cross-checks every variant and prints how they compare.
+pub fn execute(day: u32, config: &Config, options: &Options) -> Result<()> {
+    let day = find(day)?;
+
+    if options.variant.as_deref() == Some(ALL_VARIANTS) {
+        let comparison = day.compare(config, options)?;
+        println!("{}", comparison.render(config.format, config.precision)?);
+        return Ok(());
+    }
+
+    let report = day.run(config, options)?;
+    println!("{}", report.render(config.format, config.precision)?);
+    status::record_timing(config, options, &report)
+}
+
+/// Reads the input selected by `options`: an explicit file, the example fixture or the puzzle
+/// input from the config, in that order of preference. Locked inputs are decrypted on the way, and
+/// a missing puzzle input is downloaded if a session token is available.
+pub fn read_input(day: u32, config: &Config, options: &Options) -> Result<String> {
+    let path = input_path(day, config, options);
+    match fetch_missing(day, &path, config, options) {
+        Some(input) => input,
+        None => inputs::read(&path, &config.key_file),
+    }
+}
+
+/// [`read_input`], as a reader for solvers that stream their input line by line.
+pub fn open_input(day: u32, config: &Config, options: &Options) -> Result<Box<dyn BufRead>> {
+    let path = input_path(day, config, options);
+    match fetch_missing(day, &path, config, options) {
+        Some(input) => Ok(Box::new(Cursor::new(input?))),
+        None => inputs::open(&path, &config.key_file),
+    }
+}
+
+// Fetches a missing puzzle input on demand if we're able to, or leaves it to be read from disk
+fn fetch_missing(
+    day: u32,
+    path: &Path,
+    config: &Config,
+    options: &Options,
+) -> Option<Result<String>> {
+    if options.input.is_none() && !options.example && !path.exists() {
+        if let Ok(client) = Client::from_config(config) {
+            return Some(client.fetch(day, path));
+        }
+    }
+    None
+}
+
+/// Path of the input selected by `options`, without fetching or reading it.
+pub fn input_path(day: u32, config: &Config, options: &Options) -> PathBuf {
+    match &options.input {
+        Some(path) => path.clone(),
+        None if options.example => config.example_path(day),
+        None => config.input_path(day),
+    }
+}
+
+/// Looks up an enabled day, explaining why it isn't available otherwise.
+pub fn find(day: u32) -> Result<Day> {
+    match Status::of(day) {
+        Status::Enabled => registry()
+            .into_iter()
+            .find(|d| d.number == day)
+            .ok_or_else(|| anyhow!("day {} missing from registry", day)),
+        Status::Disabled => Err(anyhow!(
+            "day {} is disabled in this build (enable the `day{:02}` feature)",
+            day,
+            day
+        )),
+        Status::Unsolved if (1..=25).contains(&day) => {
+            Err(anyhow!("day {} has no solution yet", day))
+        }
+        Status::Unsolved => Err(anyhow!("{} is not a puzzle day", day)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        let expected = [
+            (1, vec!["24000", "45000"]),
+            (2, vec!["15", "12"]),
+            (3, vec!["157", "70"]),
+            (4, vec!["2", "4"]),
+            (5, vec!["CMZ", "MCD"]),
+            (6, vec!["7", "19"]),
+            (8, vec!["21", "8"]),
+            (9, vec!["13", "1"]),
+        ];
+        let options = Options {
+            example: true,
+            ..Options::default()
+        };
+
+        for (day, answers) in expected {
+            if let Ok(solver) = find(day) {
+                let input = read_input(day, &Config::default(), &options).unwrap();
+                let results: Vec<_> = solver.parts.iter().map(|p| p(&input).unwrap()).collect();
+                assert_eq!(answers, results, "day {}", day);
+            }
+        }
+    }
+
+    #[test]
+    fn test_variants_agree() {
+        let options = Options {
+            example: true,
+            ..Options::default()
+        };
+
+        for day in registry().iter().filter(|day| !day.variants.is_empty()) {
+            let comparison = day.compare(&Config::default(), &options).unwrap();
+            assert_eq!(day.variant_names().len(), comparison.runs.len());
+        }
+    }
+
+    #[test]
+    fn test_variants_disagree() {
+        let day = Day::new(1)
+            .part(|_| Ok(1))
+            .variant(Variant::new("broken").part(|_| Ok(2)));
+        let options = Options {
+            example: true,
+            ..Options::default()
+        };
+
+        let err = day.compare(&Config::default(), &options).unwrap_err();
+        assert_eq!(
+            "day 1 variants disagree on part 1: default gives 1, broken gives 2",
+            err.to_string()
+        );
+
+        let options = Options {
+            variant: Some("fast".into()),
+            ..options
+        };
+        let err = day.run(&Config::default(), &options).unwrap_err();
+        assert_eq!(
+            "day 1 has no variant 'fast' (try default, broken)",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_fetch_missing_input() {
+        use std::io::Read;
+
+        let server = crate::stub::StubServer::start(|_| (200, "A Y\nB X\nC Z\n".into()));
+        let dir = std::env::temp_dir().join(format!("aoc22-runner-{}", std::process::id()));
+        let config = Config {
+            data_dir: dir.clone(),
+            base_url: server.url.clone(),
+            session: Some("abc123".into()),
+            fetch_interval: 0,
+            ..Config::default()
+        };
+
+        let input = read_input(2, &config, &Options::default()).unwrap();
+        assert_eq!("A Y\nB X\nC Z\n", input);
+        assert!(dir.join("day02.txt").exists());
+
+        // Streaming solvers fetch the same way
+        let mut streamed = String::new();
+        open_input(3, &config, &Options::default())
+            .unwrap()
+            .read_to_string(&mut streamed)
+            .unwrap();
+        assert_eq!(input, streamed);
+        assert!(dir.join("day03.txt").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_timeout() {
+        let day = Day::new(1).part(|_| {
+            thread::sleep(std::time::Duration::from_secs(5));
+            Ok(0)
+        });
+        let config = Config {
+            timeout: Some(0),
+            ..Config::default()
+        };
+        let options = Options {
+            example: true,
+            ..Options::default()
+        };
+
+        let err = day.run(&config, &options).unwrap_err();
+        assert_eq!("day 1 timed out after 0ns", err.to_string());
+    }
+}
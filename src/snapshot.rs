@@ -0,0 +1,53 @@
+//! Snapshot assertions for error messages and rendered output.
+//!
+//! Snapshots live in `src/snapshots/<name>.snap`. A mismatch fails the test; to accept new output,
+//! rerun with `UPDATE_SNAPSHOTS=1` and review the diff before committing.
+
+use anyhow::Result;
+use std::fmt::Debug;
+use std::path::PathBuf;
+
+pub const UPDATE_VAR: &str = "UPDATE_SNAPSHOTS";
+
+pub fn assert_snapshot(name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/snapshots")
+        .join(format!("{}.snap", name));
+
+    if std::env::var(UPDATE_VAR).is_ok_and(|value| value == "1") {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    match std::fs::read_to_string(&path) {
+        Ok(expected) => assert_eq!(
+            expected, actual,
+            "snapshot '{}' changed; rerun with {}=1 to accept",
+            name, UPDATE_VAR
+        ),
+        Err(_) => panic!(
+            "no snapshot '{}' at '{}'; rerun with {}=1 to create it",
+            name,
+            path.display(),
+            UPDATE_VAR
+        ),
+    }
+}
+
+/// Lists what each input produced, so a snapshot of it shows every message in full. Inputs that
+/// unexpectedly succeed show up too, rather than failing the test outright.
+pub fn outcomes<S, T>(cases: impl IntoIterator<Item = (S, Result<T>)>) -> String
+where
+    S: AsRef<str>,
+    T: Debug,
+{
+    cases
+        .into_iter()
+        .map(|(input, result)| match result {
+            Ok(value) => format!("input: {:?}\nok: {:?}\n", input.as_ref(), value),
+            Err(err) => format!("input: {:?}\nerror: {:#}\n", input.as_ref(), err),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
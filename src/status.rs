@@ -0,0 +1,262 @@
+//! Progress overview across all 25 days, for `aoc status`.
+
+use crate::answers::Answers;
+use crate::cli::Options;
+use crate::config::Config;
+use crate::inputs;
+use crate::output::{Format, Precision, Report};
+use crate::runner::Status;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+/// The last time each day was run against its full puzzle input, in microseconds.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timings(BTreeMap<String, u64>);
+
+impl Timings {
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(path)
+            .context(format!("could not read timings '{}'", path.display()))?;
+        toml::from_str(&contents).context(format!("invalid timings '{}'", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, toml::to_string(self)?)
+            .context(format!("could not write timings '{}'", path.display()))
+    }
+
+    pub fn get(&self, day: u32) -> Option<Duration> {
+        self.0
+            .get(&format!("day{:02}", day))
+            .map(|&micros| Duration::from_micros(micros))
+    }
+
+    pub fn set(&mut self, day: u32, elapsed: Duration) {
+        self.0
+            .insert(format!("day{:02}", day), elapsed.as_micros() as u64);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Input {
+    Present,
+    Locked,
+    Missing,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayStatus {
+    pub day: u32,
+    pub solver: Status,
+    pub input: Input,
+    /// Number of parts with an answer in the registry.
+    pub verified: usize,
+    pub last_timing: Option<Duration>,
+    /// Lines of code in the day's module, not counting blanks and comments.
+    pub loc: Option<usize>,
+}
+
+pub fn gather(config: &Config) -> Result<Vec<DayStatus>> {
+    let answers = Answers::load(&config.answers_file)?;
+    let timings = Timings::load(&config.timings_file)?;
+
+    let days = (1..=25)
+        .map(|day| {
+            let input = match std::fs::read(config.input_path(day)) {
+                Ok(bytes) if inputs::is_locked(&bytes) => Input::Locked,
+                Ok(_) => Input::Present,
+                Err(_) => Input::Missing,
+            };
+            let verified = (1..=2).filter(|&p| answers.get(day, p).is_some()).count();
+
+            DayStatus {
+                day,
+                solver: Status::of(day),
+                input,
+                verified,
+                last_timing: timings.get(day),
+                loc: lines_of_code(&config.root, day),
+            }
+        })
+        .collect();
+
+    Ok(days)
+}
+
+// Counted from the sources next to the config file, so `None` when they aren't around
+fn lines_of_code(root: &Path, day: u32) -> Option<usize> {
+    let path = root.join(format!("src/days/day{:02}.rs", day));
+    let source = std::fs::read_to_string(path).ok()?;
+    let loc = source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("//"))
+        .count();
+    Some(loc)
+}
+
+pub fn render(days: &[DayStatus], format: Format, precision: Precision) -> Result<String> {
+    match format {
+        Format::Text => Ok(render_grid(days, precision)),
+        Format::Json => {
+            let days: Vec<_> = days
+                .iter()
+                .map(|status| {
+                    serde_json::json!({
+                        "day": status.day,
+                        "solver": match status.solver {
+                            Status::Enabled => "enabled",
+                            Status::Disabled => "disabled",
+                            Status::Unsolved => "none",
+                        },
+                        "input": match status.input {
+                            Input::Present => "present",
+                            Input::Locked => "locked",
+                            Input::Missing => "missing",
+                        },
+                        "verified": status.verified,
+                        "last_timing": status.last_timing.map(|t| precision.measure(t) as u64),
+                        "unit": precision.unit(),
+                        "loc": status.loc,
+                    })
+                })
+                .collect();
+            Ok(serde_json::to_string(&days)?)
+        }
+    }
+}
+
+const CELL: usize = 13;
+
+// Five weeks of five days, each day drawn as a three line cell
+fn render_grid(days: &[DayStatus], precision: Precision) -> String {
+    let border = format!("+{}", format!("{}+", "-".repeat(CELL)).repeat(5));
+    let mut lines = vec![border.clone()];
+
+    for week in days.chunks(5) {
+        let cells: Vec<_> = week.iter().map(|status| cell(status, precision)).collect();
+        for row in 0..3 {
+            let line: String = cells
+                .iter()
+                .map(|cell| format!(" {:<width$}|", cell[row], width = CELL - 1))
+                .collect();
+            lines.push(format!("|{}", line));
+        }
+        lines.push(border.clone());
+    }
+
+    lines.push("S solver, s disabled solver, I input, L locked input, * verified part".into());
+    lines.join("\n")
+}
+
+fn cell(status: &DayStatus, precision: Precision) -> [String; 3] {
+    let solver = match status.solver {
+        Status::Enabled => 'S',
+        Status::Disabled => 's',
+        Status::Unsolved => '-',
+    };
+    let input = match status.input {
+        Input::Present => 'I',
+        Input::Locked => 'L',
+        Input::Missing => '-',
+    };
+    let timing = status
+        .last_timing
+        .map(|t| format!("{} {}", precision.measure(t), precision.unit()))
+        .unwrap_or_default();
+    let loc = status
+        .loc
+        .map(|loc| format!("{} loc", loc))
+        .unwrap_or_default();
+
+    [
+        format!("{:02} {:>8}", status.day, "*".repeat(status.verified)),
+        format!("{}{} {:>8}", solver, input, timing),
+        loc,
+    ]
+}
+
+/// Remembers how long a run took, if it ran every part of the main implementation against the
+/// puzzle input.
+pub fn record_timing(config: &Config, options: &Options, report: &Report) -> Result<()> {
+    if options.part.is_some()
+        || options.example
+        || options.input.is_some()
+        || options.variant.is_some()
+    {
+        return Ok(());
+    }
+    let mut timings = Timings::load(&config.timings_file)?;
+    timings.set(report.day, report.elapsed);
+    timings.save(&config.timings_file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_grid() {
+        let days: Vec<_> = (1..=25)
+            .map(|day| DayStatus {
+                day,
+                solver: if day <= 2 {
+                    Status::Enabled
+                } else {
+                    Status::Unsolved
+                },
+                input: if day == 1 {
+                    Input::Present
+                } else {
+                    Input::Missing
+                },
+                verified: if day == 1 { 2 } else { 0 },
+                last_timing: (day == 1).then(|| Duration::from_millis(12)),
+                loc: (day <= 2).then_some(40),
+            })
+            .collect();
+
+        let grid = render_grid(&days, Precision::Ms);
+        let lines: Vec<_> = grid.lines().collect();
+
+        assert_eq!(22, lines.len());
+        assert_eq!(
+            "| 01       ** | 02          | 03          | 04          | 05          |",
+            lines[1]
+        );
+        assert_eq!(
+            "| SI    12 ms | S-          | --          | --          | --          |",
+            lines[2]
+        );
+        assert_eq!(
+            "| 40 loc      | 40 loc      |             |             |             |",
+            lines[3]
+        );
+    }
+
+    #[test]
+    fn test_gather() {
+        let days = gather(&Config::default()).unwrap();
+
+        assert_eq!(25, days.len());
+        assert!(days[8].loc.is_some());
+        assert_eq!(None, days[24].loc);
+        assert_eq!(Status::Unsolved, days[24].solver);
+
+        // An installed binary run away from the sources still reports everything else
+        let config = Config {
+            root: std::env::temp_dir(),
+            ..Config::default()
+        };
+        assert!(gather(&config)
+            .unwrap()
+            .iter()
+            .all(|status| status.loc.is_none()));
+    }
+}
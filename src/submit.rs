@@ -0,0 +1,276 @@
+//! Submitting answers, with a local journal of every attempt.
+//!
+//! The journal is what keeps us from burning attempts: an answer already known to be wrong is
+//! never sent again, and neither is one outside the range left by earlier too-high and too-low
+//! verdicts.
+
+use crate::answers::Answers;
+use crate::fetch::Client;
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, with no hint which way.
+    Wrong,
+    /// Submitted too soon after the last attempt; the answer wasn't judged.
+    Throttled,
+    /// The part was already solved on the site; the answer wasn't judged.
+    AlreadySolved,
+}
+
+impl Verdict {
+    /// Reads the verdict out of the site's response page.
+    pub fn parse(page: &str) -> Result<Self> {
+        if page.contains("That's the right answer") {
+            Ok(Verdict::Correct)
+        } else if page.contains("your answer is too high") {
+            Ok(Verdict::TooHigh)
+        } else if page.contains("your answer is too low") {
+            Ok(Verdict::TooLow)
+        } else if page.contains("That's not the right answer") {
+            Ok(Verdict::Wrong)
+        } else if page.contains("You gave an answer too recently") {
+            Ok(Verdict::Throttled)
+        } else if page.contains("You don't seem to be solving the right level") {
+            Ok(Verdict::AlreadySolved)
+        } else {
+            Err(anyhow!(
+                "could not make sense of the response to a submission"
+            ))
+        }
+    }
+
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub day: u32,
+    pub part: usize,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the Unix epoch.
+    pub time: u64,
+}
+
+/// Append-only record of every submission, stored as one JSON object per line.
+pub struct Journal {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl Journal {
+    pub fn load(path: &Path) -> Result<Self> {
+        let attempts = if path.exists() {
+            std::fs::read_to_string(path)
+                .context(format!("could not read journal '{}'", path.display()))?
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| serde_json::from_str(line).map_err(Into::into))
+                .collect::<Result<_>>()
+                .context(format!("invalid journal '{}'", path.display()))?
+        } else {
+            vec![]
+        };
+
+        Ok(Self {
+            path: path.into(),
+            attempts,
+        })
+    }
+
+    pub fn attempts(&self, day: u32, part: usize) -> impl Iterator<Item = &Attempt> {
+        self.attempts
+            .iter()
+            .filter(move |a| a.day == day && a.part == part)
+    }
+
+    pub fn record(&mut self, attempt: Attempt) -> Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .context(format!("could not open journal '{}'", self.path.display()))?;
+        writeln!(file, "{}", serde_json::to_string(&attempt)?)?;
+
+        self.attempts.push(attempt);
+        Ok(())
+    }
+
+    /// Refuses answers that earlier attempts already rule out.
+    pub fn check(&self, day: u32, part: usize, answer: &str) -> Result<()> {
+        let value: Option<i64> = answer.parse().ok();
+
+        for attempt in self.attempts(day, part) {
+            if attempt.verdict.is_wrong() && attempt.answer == answer {
+                return Err(anyhow!("'{}' is already known to be wrong", answer));
+            }
+
+            let bound: Option<i64> = attempt.answer.parse().ok();
+            match (attempt.verdict, value, bound) {
+                (Verdict::TooHigh, Some(value), Some(bound)) if value >= bound => {
+                    return Err(anyhow!("{} is too high, {} already was", value, bound))
+                }
+                (Verdict::TooLow, Some(value), Some(bound)) if value <= bound => {
+                    return Err(anyhow!("{} is too low, {} already was", value, bound))
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Submits `answer` after checking it against the journal and the answer registry, recording the
+/// attempt either way it goes. A correct answer is saved to the registry at `answers_file`.
+pub fn submit(
+    client: &Client,
+    journal: &mut Journal,
+    answers_file: &Path,
+    day: u32,
+    part: usize,
+    answer: &str,
+) -> Result<Verdict> {
+    let mut answers = Answers::load(answers_file)?;
+    match answers.get(day, part) {
+        Some(known) if known == answer => {
+            return Err(anyhow!("'{}' is already the known answer", answer))
+        }
+        Some(known) => {
+            return Err(anyhow!(
+                "'{}' conflicts with the known answer '{}'",
+                answer,
+                known
+            ))
+        }
+        None => {}
+    }
+    journal.check(day, part, answer)?;
+
+    let page = client.submit(day, part, answer)?;
+    let verdict = Verdict::parse(&page)?;
+
+    journal.record(Attempt {
+        day,
+        part,
+        answer: answer.into(),
+        verdict,
+        time: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+    })?;
+
+    if verdict == Verdict::Correct {
+        answers.set(day, part, answer);
+        answers.save(answers_file)?;
+    }
+
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetch::{RateLimit, USER_AGENT};
+    use crate::stub::StubServer;
+    use std::time::Duration;
+
+    const CORRECT: &str =
+        "<article><p>That's the right answer! You are one gold star closer.</p></article>";
+    const TOO_HIGH: &str =
+        "<article><p>That's not the right answer; your answer is too high.</p></article>";
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc22-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn client(url: &str, dir: &Path) -> Client {
+        let limit = RateLimit {
+            interval: Duration::ZERO,
+            stamp: dir.join(".last-fetch"),
+        };
+        Client::new(url, 2022, "abc123", USER_AGENT, limit)
+    }
+
+    #[test]
+    fn test_submit() {
+        let server = StubServer::start(|request| match request.body.as_str() {
+            "level=1&answer=24000" => (200, CORRECT.into()),
+            _ => (200, TOO_HIGH.into()),
+        });
+        let dir = temp_dir("submit");
+        let client = client(&server.url, &dir);
+        let (journal_file, answers_file) = (dir.join("journal.jsonl"), dir.join("answers.toml"));
+        let mut journal = Journal::load(&journal_file).unwrap();
+
+        let verdict = submit(&client, &mut journal, &answers_file, 1, 1, "30000").unwrap();
+        assert_eq!(Verdict::TooHigh, verdict);
+
+        // Ruled out by the journal, so these never reach the server
+        assert!(submit(&client, &mut journal, &answers_file, 1, 1, "30000").is_err());
+        assert!(submit(&client, &mut journal, &answers_file, 1, 1, "31000").is_err());
+        assert_eq!(1, server.requests().len());
+
+        let verdict = submit(&client, &mut journal, &answers_file, 1, 1, "24000").unwrap();
+        assert_eq!(Verdict::Correct, verdict);
+        assert_eq!(
+            Some("24000"),
+            Answers::load(&answers_file).unwrap().get(1, 1)
+        );
+
+        let requests = server.requests();
+        assert_eq!(2, requests.len());
+        assert_eq!("POST", requests[1].method);
+        assert_eq!("/2022/day/1/answer", requests[1].path);
+
+        let journal = Journal::load(&journal_file).unwrap();
+        let verdicts: Vec<_> = journal.attempts(1, 1).map(|a| a.verdict).collect();
+        assert_eq!(vec![Verdict::TooHigh, Verdict::Correct], verdicts);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_check() {
+        let dir = temp_dir("journal");
+        let mut journal = Journal::load(&dir.join("journal.jsonl")).unwrap();
+        for (answer, verdict) in [("100", Verdict::TooHigh), ("10", Verdict::TooLow)] {
+            let attempt = Attempt {
+                day: 9,
+                part: 2,
+                answer: answer.into(),
+                verdict,
+                time: 0,
+            };
+            journal.record(attempt).unwrap();
+        }
+
+        assert!(journal.check(9, 2, "50").is_ok());
+        assert!(journal.check(9, 2, "100").is_err());
+        assert!(journal.check(9, 2, "150").is_err());
+        assert!(journal.check(9, 2, "10").is_err());
+        assert!(journal.check(9, 2, "-5").is_err());
+        assert!(journal.check(9, 1, "150").is_ok());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_parse_verdict() {
+        assert_eq!(Verdict::Correct, Verdict::parse(CORRECT).unwrap());
+        assert_eq!(Verdict::TooHigh, Verdict::parse(TOO_HIGH).unwrap());
+        assert!(Verdict::parse("<html></html>").is_err());
+    }
+}